@@ -0,0 +1,131 @@
+use bevy::prelude::*;
+
+use crate::input::{Action, ActionState};
+use crate::preference::{ApplyBookmarksEvent, CameraBookmark, Preferences};
+
+use super::{ActiveViewport, CameraController, CameraView, FloorLevel};
+
+/// Stores the current editor camera view under the given name, replacing a bookmark with the same name.
+#[derive(Debug, Event)]
+pub struct SaveBookmarkEvent(pub String);
+
+/// Animates the editor camera to the bookmark with the given name.
+#[derive(Debug, Event)]
+pub struct RecallBookmarkEvent(pub String);
+
+/// Removes the bookmark with the given name.
+#[derive(Debug, Event)]
+pub struct DeleteBookmarkEvent(pub String);
+
+pub struct BookmarkPlugin;
+
+impl Plugin for BookmarkPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SaveBookmarkEvent>()
+            .add_event::<RecallBookmarkEvent>()
+            .add_event::<DeleteBookmarkEvent>()
+            .add_systems(
+                Update,
+                (
+                    quick_bookmark_shortcut,
+                    (save_bookmark, recall_bookmark, delete_bookmark),
+                )
+                    .chain(),
+            );
+    }
+}
+
+impl From<&CameraBookmark> for CameraView {
+    fn from(bookmark: &CameraBookmark) -> Self {
+        Self {
+            yaw: bookmark.yaw,
+            pitch: bookmark.pitch,
+            distance: bookmark.distance,
+            point_of_view: bookmark.point_of_view.into(),
+        }
+    }
+}
+
+impl CameraView {
+    pub fn to_bookmark(&self, name: impl Into<String>) -> CameraBookmark {
+        CameraBookmark {
+            name: name.into(),
+            yaw: self.yaw,
+            pitch: self.pitch,
+            distance: self.distance,
+            point_of_view: self.point_of_view.into(),
+        }
+    }
+}
+
+/// Quick bookmarks "View 1" to "View 9".
+fn quick_bookmark_shortcut(
+    action_state: Res<ActionState>,
+    mut save_event: EventWriter<SaveBookmarkEvent>,
+    mut recall_event: EventWriter<RecallBookmarkEvent>,
+) {
+    for index in 1..=9 {
+        let name = || format!("View {index}");
+        if action_state.just_pressed(Action::SaveBookmark(index)) {
+            save_event.send(SaveBookmarkEvent(name()));
+        }
+        if action_state.just_pressed(Action::RecallBookmark(index)) {
+            recall_event.send(RecallBookmarkEvent(name()));
+        }
+    }
+}
+
+fn save_bookmark(
+    mut event: EventReader<SaveBookmarkEvent>,
+    mut preferences: ResMut<Preferences>,
+    mut apply_event: EventWriter<ApplyBookmarksEvent>,
+    query: Query<&CameraController, With<ActiveViewport>>,
+) {
+    for SaveBookmarkEvent(name) in event.read() {
+        let Ok(controller) = query.get_single() else {
+            log::warn!("no editor camera to bookmark");
+            continue;
+        };
+        let bookmark = controller.view().to_bookmark(name.clone());
+
+        let bookmarks = &mut preferences.camera_bookmarks;
+        if let Some(existing) = bookmarks.iter_mut().find(|b| &b.name == name) {
+            *existing = bookmark;
+        } else {
+            bookmarks.push(bookmark);
+        }
+
+        apply_event.send(ApplyBookmarksEvent);
+    }
+}
+
+fn recall_bookmark(
+    mut event: EventReader<RecallBookmarkEvent>,
+    preferences: Res<Preferences>,
+    floor_level: Res<FloorLevel>,
+    mut query: Query<&mut CameraController, With<ActiveViewport>>,
+) {
+    for RecallBookmarkEvent(name) in event.read() {
+        let Some(bookmark) = preferences.camera_bookmarks.iter().find(|b| &b.name == name) else {
+            log::warn!("bookmark '{name}' not found");
+            continue;
+        };
+
+        let mut target: CameraView = bookmark.into();
+        target.clamp_pitch(floor_level.0, &preferences.camera_control);
+        for mut controller in query.iter_mut() {
+            controller.transition_to(target, preferences.camera_control.transition_duration);
+        }
+    }
+}
+
+fn delete_bookmark(
+    mut event: EventReader<DeleteBookmarkEvent>,
+    mut preferences: ResMut<Preferences>,
+    mut apply_event: EventWriter<ApplyBookmarksEvent>,
+) {
+    for DeleteBookmarkEvent(name) in event.read() {
+        preferences.camera_bookmarks.retain(|b| &b.name != name);
+        apply_event.send(ApplyBookmarksEvent);
+    }
+}
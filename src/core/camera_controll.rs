@@ -1,13 +1,41 @@
 use bevy::prelude::*;
-use bevy::input::mouse::{MouseMotion, MouseWheel};
+use bevy::ecs::system::SystemParam;
+use bevy::input::mouse::MouseMotion;
+use bevy::window::{CursorMoved, PrimaryWindow};
 
 use crate::check_zero_warn;
-use crate::input::ExtendedButtonInput;
+use crate::input::{Action, ActionState, InputFocus};
 use crate::preference::{ApplyPreferencesEvent, Preferences};
 
+use super::{
+    glide, impulse, track, viewport_ray, ActiveViewport, BookmarkPlugin, CameraInertia,
+    CameraTransition, CameraTransitionPlugin, ClippingPlugin, FloorLevel, FramingPlugin,
+    GamepadNavigation, GamepadNavigationPlugin, GestureInput, GestureSteps, OrbitPivotPlugin,
+    PlanViewPlugin, SceneBounds, SceneRaycast, StandardViewPlugin, ViewportPlugin, Walk,
+    WalkthroughPlugin,
+};
+
+/// Logarithmic zoom of a wheel step per unit of zoom sensitivity,
+/// the default sensitivity zooms about 10% a step.
+const WHEEL_ZOOM_SCALE: f32 = 0.2;
+/// Logarithmic zoom of a pixel of a zoom drag per unit of zoom sensitivity.
+const DRAG_ZOOM_SCALE: f32 = 0.01;
+
 #[derive(Component)]
 pub struct EditorCamera;
 
+/// How the [`CameraController`] projects and moves the camera.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum CameraMode {
+    /// Perspective orbit around the point of view.
+    #[default]
+    Orbit,
+    /// Orthographic top-down plan view, rotation is limited to the vertical axis.
+    Plan,
+    /// First-person walk at eye level, see [`Walk`].
+    Walkthrough,
+}
+
 /// Component to store the camera's control state.
 #[derive(Component, Default, Reflect)]
 #[reflect(Component)]
@@ -18,52 +46,54 @@ pub struct CameraController {
     pub point_of_view: Vec3,
     pub is_rotating: bool,
     pub is_panning: bool,
-    pub rotating_inertia: InertiaRotation,
+    pub inertia: CameraInertia,
+    pub mode: CameraMode,
+    /// Perspective projection to restore when leaving the plan view.
+    pub perspective: PerspectiveProjection,
+    /// Running animation towards another view, cancelled by any camera input.
+    pub transition: Option<CameraTransition>,
+    pub walk: Walk,
+    /// Point picked under the cursor the current rotation swings around.
+    pub pivot: Option<Vec3>,
 }
 
 impl CameraController {
-  fn rotate(&mut self, delta: Vec2, sensitivity: f32) {
-    self.yaw -= delta.x * sensitivity;
-    self.pitch -= delta.y * sensitivity;
-    self.pitch = self
-        .pitch
-        .clamp(-89.9_f32.to_radians(), 89.9_f32.to_radians());
+  /// Orientation of the camera, it looks at the point of view along its local -Z.
+  pub fn rotation(&self) -> Quat {
+    Quat::from_axis_angle(Vec3::Y, self.yaw) * Quat::from_axis_angle(Vec3::X, self.pitch)
   }
-}
 
-#[derive(Default, Reflect)]
-struct InertiaRotation {
-    speed: f32,
-    direction: Vec2,
-    /// neccessary to count inertia affter action finish
-    start_second: f32,
-    /// neccessary to count inertia affter action finish
-    start_yaw: f32,
-    /// neccessary to count inertia affter action finish
-    start_pitch: f32,
-}
-
-impl InertiaRotation {
-  fn is_present(&self) -> bool {
-    self.speed != 0.
-  }
+  /// Turns the camera by a yaw and pitch step in radians.
+  pub(super) fn rotate(&mut self, step: Vec2) {
+    self.yaw += step.x;
 
-  fn set_start(&mut self, second: f32, yaw: f32, pitch: f32) {
-    self.start_second = second;
-    self.start_yaw = yaw;
-    self.start_pitch = pitch;
-  }
-
-  fn deplete(&mut self) {
-    *self = Self::default();
+    // The plan view only spins around the vertical axis.
+    if self.mode == CameraMode::Orbit {
+        self.pitch += step.y;
+        self.pitch = self
+            .pitch
+            .clamp(-89.9_f32.to_radians(), 89.9_f32.to_radians());
+    }
   }
 
-  fn decrement(&mut self, decrement_speed: f32) {
-    self.speed -= decrement_speed;
+  /// Point of view movement that drags the scene by `drag` pixels. The orthographic
+  /// projection follows the pixels exactly, the perspective one scales with the distance.
+  fn pan_step(
+      &self,
+      transform: &Transform,
+      projection: &Projection,
+      drag: Vec2,
+      pan_sensitivity: f32,
+  ) -> Vec3 {
+    // Calculate right and up vectors relative to the camera's orientation.
+    let right = transform.rotation * Vec3::X;
+    let up = transform.rotation * Vec3::Y;
 
-    if self.speed < 0. {
-        self.speed = 0.;
-    }
+    let scale = match projection {
+        Projection::Orthographic(orthographic) => orthographic.scale,
+        _ => pan_sensitivity * self.distance / 10.0,
+    };
+    -(right * drag.x - up * drag.y) * scale
   }
 }
 
@@ -73,101 +103,421 @@ impl Plugin for CameraControllPlugin {
   fn build(&self, app: &mut App) {
     app
       .register_type::<CameraController>()
+      .add_plugins((
+          PlanViewPlugin,
+          FramingPlugin,
+          CameraTransitionPlugin,
+          BookmarkPlugin,
+          WalkthroughPlugin,
+          StandardViewPlugin,
+          OrbitPivotPlugin,
+          ViewportPlugin,
+          GamepadNavigationPlugin,
+          ClippingPlugin,
+      ))
       .add_systems(Update, update_camera_controller);
   }
 }
 
+/// Input the camera controller reads: the bound actions, the pointer, the gestures
+/// and the gamepad.
+#[derive(SystemParam)]
+pub(super) struct CameraInput<'w, 's> {
+    action_state: Res<'w, ActionState>,
+    input_focus: Res<'w, InputFocus>,
+    gamepad: Res<'w, GamepadNavigation>,
+    gesture_input: GestureInput<'w, 's>,
+    mouse_motion_events: EventReader<'w, 's, MouseMotion>,
+    cursor_moved_events: EventReader<'w, 's, CursorMoved>,
+    window_query: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+}
+
 /// System to update the camera based on user input.
-fn update_camera_controller(
-    mut mouse_motion_events: EventReader<MouseMotion>,
-    mut mouse_wheel_events: EventReader<MouseWheel>,
+pub(super) fn update_camera_controller(
+    input: CameraInput,
     preferences: Res<Preferences>,
-    mouse_button_input: Res<ButtonInput<MouseButton>>,
-    extra_mouse_button_input: Res<ExtendedButtonInput>,
-    mut query: Query<(&mut CameraController, &mut Transform)>,
+    mut query: Query<(
+        &mut CameraController,
+        &mut Transform,
+        &mut Projection,
+        &Camera,
+        Has<ActiveViewport>,
+    )>,
+    scene_raycast: SceneRaycast,
+    scene_bounds: Res<SceneBounds>,
+    floor_level: Res<FloorLevel>,
     time: Res<Time>,
 ) {
-    let preferences = &preferences.camera_controll;
-    for (mut controller, mut transform) in query.iter_mut() {
-        // Handle zoom with mouse wheel.
-        for event in mouse_wheel_events.read() {
-            controller.distance -= event.y * preferences.zoom_sensitivity;
-            controller.distance = 
-                controller.distance.clamp(preferences.min_distance, preferences.max_distance);
+    let CameraInput {
+        action_state,
+        input_focus,
+        gamepad,
+        mut gesture_input,
+        mut mouse_motion_events,
+        mut cursor_moved_events,
+        window_query,
+    } = input;
+    let preferences = &preferences.camera_control;
+    let delta_seconds = time.delta_seconds();
+    let cursor_position = window_query
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position());
+
+    let gestures = gesture_input.read();
+
+    // Calculate the total mouse movement since the last frame.
+    let mut delta = Vec2::ZERO;
+    for event in mouse_motion_events.read() {
+        delta += event.delta;
+    }
+
+    // Cursor movement in logical pixels, used where the scene has to follow the cursor exactly.
+    let mut cursor_delta = Vec2::ZERO;
+    for event in cursor_moved_events.read() {
+        cursor_delta += event.delta.unwrap_or_default();
+    }
+
+    // Grabbing the scene stops the glide, any camera input takes the control
+    // back from a running transition.
+    let grabbed = [Action::CameraOrbit, Action::CameraPan, Action::CameraZoom]
+        .into_iter()
+        .any(|action| action_state.just_pressed(action));
+    let wheel = action_state.value(Action::ZoomIn) - action_state.value(Action::ZoomOut);
+    // Drags and wheel over the egui windows belong to them.
+    let scene_has_pointer = input_focus.scene_has_pointer();
+    let camera_input = grabbed
+        || wheel != 0.
+        || gestures.pan != Vec2::ZERO
+        || gestures.pinch != 0.
+        || gestures.rotation != 0.;
+
+    for (mut controller, mut transform, mut projection, camera, active) in query.iter_mut() {
+        // The walkthrough moves the camera on its own.
+        if controller.mode == CameraMode::Walkthrough {
+            continue;
         }
 
-        // Update rotation and panning states based on mouse button input.
-        controller.is_rotating = extra_mouse_button_input.held(MouseButton::Right);
-        controller.is_panning = extra_mouse_button_input.held(MouseButton::Middle);
+        // The gamepad drives the active viewport wherever the cursor is.
+        let gamepad = if active {
+            *gamepad
+        } else {
+            GamepadNavigation::default()
+        };
+        if !gamepad.is_idle() {
+            controller.cancel_transition();
+        }
 
-        // Calculate the total mouse movement since the last frame.
-        let mut delta = Vec2::ZERO;
-        for event in mouse_motion_events.read() {
-            delta += event.delta;
+        // Only the viewport under the cursor listens to the input,
+        // the others keep gliding and finish their transitions.
+        let active = active && scene_has_pointer;
+        let (gestures, wheel, delta, cursor_delta) = if active {
+            (gestures, wheel, delta, cursor_delta)
+        } else {
+            (GestureSteps::default(), 0., Vec2::ZERO, Vec2::ZERO)
+        };
+
+        let viewport_height = camera.logical_viewport_size().map(|size| size.y);
+        // Cursor in the viewport's own coordinates.
+        let cursor_position = cursor_position
+            .zip(camera.logical_viewport_rect())
+            .map(|(cursor, rect)| cursor - rect.min);
+
+        if active && camera_input {
+            controller.cancel_transition();
         }
+        if active && grabbed {
+            controller.inertia.rotation = Vec2::ZERO;
+            controller.inertia.pan = Vec3::ZERO;
+        }
+
+        // Update rotation and panning states based on the camera drag actions.
+        let was_rotating = controller.is_rotating;
+        controller.is_rotating = active && action_state.pressed(Action::CameraOrbit);
+        controller.is_panning = active && action_state.pressed(Action::CameraPan);
+        let is_zooming = active && action_state.pressed(Action::CameraZoom);
 
-        if preferences.inertia_on { 
-          if mouse_button_input.just_pressed(MouseButton::Right) {
-            controller.rotating_inertia.deplete();
-            let seconds = time.elapsed().as_secs_f32();
-            let yew = controller.yaw;
-            let pitch = controller.pitch;
-            controller.rotating_inertia.set_start(seconds, yew, pitch);
-          }
-          if mouse_button_input.just_released(MouseButton::Right) {
-            let seconds = time.elapsed().as_secs_f32();
-
-             // Calculate the difference in yaw and pitch
-             let delta_yaw = controller.yaw - controller.rotating_inertia.start_yaw;
-             let delta_pitch = controller.pitch - controller.rotating_inertia.start_pitch;
-
-             // Calculate the rotated distance (Euclidean distance between two angles)
-             let rotated_distance = (delta_yaw.powi(2) + delta_pitch.powi(2)).sqrt();
-
-             let seconds_passed = seconds - controller.rotating_inertia.start_second;
-
-             if seconds_passed != 0. {
-               controller.rotating_inertia.speed =
-                   rotated_distance / seconds_passed;
-
-               controller.rotating_inertia.direction = delta.normalize_or_zero();
-             }
-          }
-          if controller.rotating_inertia.is_present() {
-            let direction = controller.rotating_inertia.direction;
-            let speed = controller.rotating_inertia.speed;
-
-            controller.rotate(delta, preferences.rotation_sensitivity);
-            controller.rotating_inertia.decrement(preferences.inertia_decrement_speed);
-          }
+        // Pick the orbit pivot under the cursor as the rotation starts.
+        if controller.is_rotating && !was_rotating && preferences.orbit_around_cursor {
+            let pivot = cursor_position
+                .and_then(|cursor| viewport_ray(camera, &transform, cursor))
+                .and_then(|ray| scene_raycast.cast(ray));
+
+            if let Some(pivot) = pivot {
+                controller.set_pivot(
+                    pivot,
+                    &transform,
+                    preferences.min_distance,
+                    preferences.max_distance,
+                );
+            }
+        }
+
+        // Zoom steps are multiplicative, they change the logarithm of the distance,
+        // so a wheel step zooms as much in a room as over the whole building.
+        let mut zoom_step = -wheel * preferences.zoom_sensitivity * WHEEL_ZOOM_SCALE;
+        if is_zooming {
+            zoom_step += delta.y * preferences.zoom_sensitivity * DRAG_ZOOM_SCALE;
         }
 
-        // Rotate the camera around the point of view.
+        let mut rotation_step = Vec2::ZERO;
         if controller.is_rotating {
-            controller.rotate(delta, preferences.rotation_sensitivity);
+            rotation_step = -delta * preferences.rotation_sensitivity;
         }
 
-        // Pan the point of view.
+        let mut pan_step = Vec3::ZERO;
         if controller.is_panning {
-            // Calculate right and up vectors relative to the camera's orientation.
-            let right = transform.rotation * Vec3::X;
-            let up = transform.rotation * Vec3::Y * -1.;
-
-            let distance = controller.distance;
-            // Adjust the point of view based on mouse movement.
-            controller.point_of_view -= (right * delta.x * preferences.pan_sensitivity
-                + up * delta.y * preferences.pan_sensitivity)
-                * distance / 10.0;
+            // The plan stays exactly under the cursor.
+            let drag = match projection.as_ref() {
+                Projection::Orthographic(_) => cursor_delta,
+                _ => delta,
+            };
+            pan_step = controller.pan_step(&transform, &projection, drag, preferences.pan_sensitivity);
+        }
+
+        if preferences.inertia_on {
+            let damping = preferences.inertia_damping;
+            let (is_rotating, is_panning) = (controller.is_rotating, controller.is_panning);
+            let inertia = &mut controller.inertia;
+
+            // The wheel only gives the zoom a push, the glide covers the whole step.
+            impulse(&mut inertia.zoom, zoom_step, damping);
+            let zoom_speed = inertia.zoom.abs();
+            zoom_step = glide(&mut inertia.zoom, zoom_speed, damping, delta_seconds);
+
+            if is_rotating {
+                track(&mut inertia.rotation, rotation_step, delta_seconds);
+            } else {
+                let speed = inertia.rotation.length();
+                rotation_step = glide(&mut inertia.rotation, speed, damping, delta_seconds);
+            }
+
+            if is_panning {
+                track(&mut inertia.pan, pan_step, delta_seconds);
+            } else {
+                let speed = inertia.pan.length();
+                pan_step = glide(&mut inertia.pan, speed, damping, delta_seconds);
+            }
+        } else {
+            controller.inertia.stop();
+        }
+
+        // Touchpads and touchscreens bring their own momentum, gestures skip the inertia.
+        zoom_step -= (gestures.pinch * preferences.pinch_sensitivity).max(-0.5).ln_1p();
+        rotation_step.x += gestures.rotation * preferences.touch_rotation_sensitivity;
+        pan_step += controller.pan_step(
+            &transform,
+            &projection,
+            gestures.pan * preferences.touch_pan_sensitivity,
+            preferences.pan_sensitivity,
+        );
+
+        // Sticks and triggers set speeds, relative to the orbit distance for moves and zoom.
+        let gamepad_speed = preferences.gamepad_move_sensitivity * delta_seconds;
+        zoom_step -= gamepad.zoom * gamepad_speed;
+        rotation_step += Vec2::new(-gamepad.look.x, gamepad.look.y)
+            * preferences.gamepad_look_sensitivity
+            * delta_seconds;
+        // The left stick moves across the floor, forward is where the camera faces.
+        let heading = Quat::from_axis_angle(Vec3::Y, controller.yaw);
+        pan_step += heading
+            * Vec3::new(gamepad.movement.x, 0., -gamepad.movement.y)
+            * gamepad_speed
+            * controller.distance;
+
+        // Handle zoom.
+        let (zoom_limited, zoom_ratio) = match (controller.mode, projection.as_mut()) {
+            (CameraMode::Plan, Projection::Orthographic(orthographic)) => {
+                // Zoom in the plan view scales the projection, within the same
+                // limits as the orbit distance.
+                let pixel_size = controller.pixel_size(viewport_height);
+                let previous = orthographic.scale;
+                let scale = orthographic.scale * zoom_step.exp();
+                orthographic.scale = scale.clamp(
+                    preferences.min_distance * pixel_size,
+                    preferences.max_distance * pixel_size,
+                );
+                (orthographic.scale != scale, orthographic.scale / previous)
+            }
+            _ => {
+                let previous = controller.distance;
+                let distance = controller.distance * zoom_step.exp();
+                controller.distance =
+                    distance.clamp(preferences.min_distance, preferences.max_distance);
+                (controller.distance != distance, controller.distance / previous)
+            }
+        };
+        // Don't keep pushing against the zoom limit.
+        if zoom_limited {
+            controller.inertia.zoom = 0.;
+        }
+
+        // Scale the view around the point under the cursor so it stays in place.
+        if preferences.zoom_to_cursor && zoom_ratio.is_finite() && zoom_ratio != 1. {
+            let target = cursor_position
+                .and_then(|cursor| viewport_ray(camera, &transform, cursor))
+                .and_then(|ray| scene_raycast.cast(ray));
+
+            if let Some(target) = target {
+                let mut point_of_view = target + (controller.point_of_view - target) * zoom_ratio;
+                // The plan view camera height is fixed, only move across the plan.
+                if controller.mode == CameraMode::Plan {
+                    point_of_view.y = controller.point_of_view.y;
+                }
+                controller.point_of_view = point_of_view;
+            }
+        }
+
+        // Rotate the camera around the point of view or the picked pivot.
+        controller.orbit(rotation_step);
+        if !controller.is_rotating && controller.inertia.rotation == Vec2::ZERO {
+            controller.pivot = None;
+        }
+
+        // Pan the point of view.
+        controller.point_of_view += pan_step;
+
+        // Ease back inside the limits, a running transition knows where it is going.
+        if preferences.limits_on && controller.transition.is_none() {
+            controller.apply_limits(scene_bounds.0, floor_level.0, preferences, delta_seconds);
         }
 
         // Update the camera's transform to match the controller's state.
-        let rotation = Quat::from_axis_angle(Vec3::Y, controller.yaw)
-            * Quat::from_axis_angle(Vec3::X, controller.pitch);
+        let rotation = controller.rotation();
+
+        // The plan view keeps the camera high above the plan so walls are never clipped,
+        // the orthographic projection makes the height invisible.
+        let distance = match controller.mode {
+            CameraMode::Plan => preferences.max_distance,
+            _ => controller.distance,
+        };
 
-        let offset = rotation * Vec3::new(0.0, 0.0, controller.distance);
+        let offset = rotation * Vec3::new(0.0, 0.0, distance);
 
         transform.translation = controller.point_of_view + offset;
 
-        transform.look_at(controller.point_of_view, Vec3::Y);
+        // The camera looks along its local -Z, so the orbit rotation already faces the
+        // point of view, even straight down where `look_at` has no valid up vector.
+        transform.rotation = rotation;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::input::InputPlugin;
+    use bevy::window::CursorMoved;
+
+    use crate::core::{ActiveViewport, FloorLevel, GamepadNavigation, SceneBounds};
+    use crate::input::{
+        ActionPlugin, ExtendedButtonInputPlugin, InputFocus, InputRecording, InputRecordingPlugin,
+        InputReplay,
+    };
+    use crate::preference::Preferences;
+
+    use super::*;
+
+    fn load_recording(name: &str) -> InputRecording {
+        let path = format!(
+            "{}/tests/fixtures/recordings/{name}.yaml",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        InputRecording::load(path.as_ref()).unwrap_or_else(|err| panic!("{path}: {err}"))
+    }
+
+    /// Headless app replaying `recording` into a single active camera, with the
+    /// recorded frame times or `fixed_step`.
+    fn replay_app(recording: InputRecording, fixed_step: Option<Duration>) -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            InputPlugin,
+            ExtendedButtonInputPlugin::<MouseButton>::default(),
+            ExtendedButtonInputPlugin::<KeyCode>::default(),
+            ActionPlugin,
+            InputRecordingPlugin,
+        ))
+        .init_resource::<Preferences>()
+        .init_resource::<InputFocus>()
+        .init_resource::<GamepadNavigation>()
+        .init_resource::<SceneBounds>()
+        .init_resource::<FloorLevel>()
+        .add_event::<CursorMoved>()
+        .add_systems(Update, update_camera_controller);
+
+        app.world_mut().spawn((Window::default(), PrimaryWindow));
+        app.world_mut().spawn((
+            CameraController {
+                distance: 10.,
+                point_of_view: Vec3::new(0., 2., 0.),
+                ..default()
+            },
+            Transform::default(),
+            Projection::default(),
+            Camera::default(),
+            ActiveViewport,
+        ));
+
+        let mut replay = InputReplay::new(recording);
+        replay.exit_when_done = true;
+        replay.fixed_step = fixed_step;
+        replay.start(app.world_mut());
+        app
+    }
+
+    /// A right drag that orbits and glides out, then three wheel steps in.
+    #[test]
+    fn replay_reproduces_the_camera() {
+        let mut app = replay_app(load_recording("orbit_and_zoom"), None);
+        while app.should_exit().is_none() {
+            app.update();
+        }
+
+        let controller = app
+            .world_mut()
+            .query::<&CameraController>()
+            .single(app.world());
+        let close = |value: f32, expected: f32| (value - expected).abs() < 1e-4;
+        assert!(close(controller.yaw, -0.72), "yaw {}", controller.yaw);
+        assert!(
+            close(controller.pitch, -0.22086),
+            "pitch {}",
+            controller.pitch
+        );
+        assert!(
+            close(controller.distance, 7.40818),
+            "distance {}",
+            controller.distance
+        );
+        assert_eq!(controller.point_of_view, Vec3::new(0., 2., 0.));
+    }
+
+    /// The same replay at a fixed time step, the orbit and the zoom don't depend on it.
+    #[test]
+    fn replay_steps_at_the_fixed_step() {
+        let step = Duration::from_millis(10);
+        let mut app = replay_app(load_recording("orbit_and_zoom"), Some(step));
+        // The clock starts with the first update.
+        app.update();
+        loop {
+            app.update();
+            if !app.world().contains_resource::<InputReplay>() {
+                break;
+            }
+            assert_eq!(app.world().resource::<Time>().delta(), step);
+        }
+
+        let controller = app
+            .world_mut()
+            .query::<&CameraController>()
+            .single(app.world());
+        let close = |value: f32, expected: f32| (value - expected).abs() < 1e-4;
+        assert!(close(controller.yaw, -0.72), "yaw {}", controller.yaw);
+        assert!(
+            close(controller.distance, 7.40818),
+            "distance {}",
+            controller.distance
+        );
     }
 }
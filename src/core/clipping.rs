@@ -0,0 +1,78 @@
+use bevy::math::bounding::Aabb3d;
+use bevy::prelude::*;
+use bevy::render::camera::CameraUpdateSystem;
+
+use crate::CM;
+
+use super::{CameraController, CameraMode, EditorCamera, SceneBounds};
+
+/// Closest the near plane gets, walls in the walkthrough are never nearer.
+const MIN_NEAR_CM: f32 = 1.0;
+/// Near plane as a part of the orbit distance, far enough out to keep thin boards
+/// from z-fighting and close enough that nothing around the point of view is cut.
+const NEAR_FRACTION: f32 = 0.01;
+/// Room behind the farthest corner of the scene.
+const FAR_MARGIN: f32 = 1.1;
+
+pub struct ClippingPlugin;
+
+impl Plugin for ClippingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            update_clipping_planes.before(CameraUpdateSystem),
+        );
+    }
+}
+
+/// Distances from `position` to the nearest and the farthest point of `bounds`.
+fn depth_range(bounds: Aabb3d, position: Vec3) -> (f32, f32) {
+    let nearest = Vec3::from(bounds.closest_point(position)).distance(position);
+
+    let (min, max) = (Vec3::from(bounds.min), Vec3::from(bounds.max));
+    let farthest_corner = Vec3::select(
+        (position - min).abs().cmpgt((position - max).abs()),
+        min,
+        max,
+    );
+    (nearest, farthest_corner.distance(position))
+}
+
+/// Fits the near and far planes of the editor cameras around the orbit distance and the scene.
+fn update_clipping_planes(
+    scene_bounds: Res<SceneBounds>,
+    mut query: Query<(&CameraController, &Transform, &mut Projection), With<EditorCamera>>,
+) {
+    for (controller, transform, mut projection) in query.iter_mut() {
+        let (nearest, farthest) = scene_bounds.0.map_or((f32::MAX, 0.), |bounds| {
+            depth_range(bounds, transform.translation)
+        });
+        // The walkthrough eye has no point of view to keep in focus.
+        let focus = match controller.mode {
+            CameraMode::Walkthrough => 0.,
+            _ => controller.distance,
+        };
+        // The plan view camera hovers high above the point of view, keep that in range too.
+        let far =
+            farthest.max(transform.translation.distance(controller.point_of_view)) * FAR_MARGIN;
+
+        match projection.bypass_change_detection() {
+            Projection::Perspective(perspective) => {
+                let near = (focus * NEAR_FRACTION).min(nearest).max(MIN_NEAR_CM * CM);
+                if perspective.near == near && perspective.far == far {
+                    continue;
+                }
+                perspective.near = near;
+                perspective.far = far;
+            }
+            // Orthographic depth is linear, only the far plane has to reach the floor.
+            Projection::Orthographic(orthographic) => {
+                if orthographic.far == far {
+                    continue;
+                }
+                orthographic.far = far;
+            }
+        }
+        projection.set_changed();
+    }
+}
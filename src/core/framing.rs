@@ -0,0 +1,117 @@
+use bevy::math::bounding::{Aabb3d, BoundingVolume};
+use bevy::prelude::*;
+use bevy::render::camera::CameraUpdateSystem;
+
+use crate::input::{Action, ActionState};
+use crate::preference::Preferences;
+
+use super::{
+    merged_bounds, update_scene_bounds, CameraController, CameraMode, EditorCamera, MeshBounds,
+    SceneBounds, Selected,
+};
+
+/// What a [`FrameEvent`] fits into the viewport.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameTarget {
+    /// Every mesh in the scene.
+    All,
+    /// Meshes marked [`Selected`] by a click, the whole scene when nothing is selected.
+    Selected,
+}
+
+/// Moves the editor camera so the target fits the viewport.
+#[derive(Debug, Event)]
+pub struct FrameEvent(pub FrameTarget);
+
+pub struct FramingPlugin;
+
+impl Plugin for FramingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<FrameEvent>()
+            .add_systems(Startup, frame_on_startup)
+            .add_systems(Update, frame_shortcut)
+            // Mesh bounds and viewport sizes are only known late in the frame.
+            .add_systems(
+                PostUpdate,
+                frame_target
+                    .after(update_scene_bounds)
+                    .after(CameraUpdateSystem),
+            );
+    }
+}
+
+impl CameraController {
+    /// Centers the point of view on `bounds` and zooms so the whole box is visible
+    /// with the current field of view.
+    pub fn frame(
+        &mut self,
+        bounds: Aabb3d,
+        projection: &mut Projection,
+        viewport_size: Option<Vec2>,
+        min_distance: f32,
+        max_distance: f32,
+    ) {
+        self.leave_walkthrough();
+        self.point_of_view = bounds.center().into();
+        let radius = bounds.half_size().length();
+
+        match (self.mode, projection) {
+            (CameraMode::Plan, Projection::Orthographic(orthographic)) => {
+                let pixel_size = self.pixel_size(viewport_size.map(|size| size.y));
+                let viewport = viewport_size.map_or(1., |size| size.min_element().max(1.));
+                orthographic.scale = (2. * radius / viewport)
+                    .clamp(min_distance * pixel_size, max_distance * pixel_size);
+            }
+            (_, Projection::Perspective(perspective)) => {
+                // The narrower of the vertical and horizontal field of view decides.
+                let half_fov = (perspective.fov / 2.)
+                    .min(((perspective.fov / 2.).tan() * perspective.aspect_ratio).atan());
+                self.distance = (radius / half_fov.sin()).clamp(min_distance, max_distance);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn frame_on_startup(mut event: EventWriter<FrameEvent>) {
+    event.send(FrameEvent(FrameTarget::All));
+}
+
+fn frame_shortcut(action_state: Res<ActionState>, mut event: EventWriter<FrameEvent>) {
+    if action_state.just_pressed(Action::FrameAll) {
+        event.send(FrameEvent(FrameTarget::All));
+    }
+    if action_state.just_pressed(Action::FrameSelected) {
+        event.send(FrameEvent(FrameTarget::Selected));
+    }
+}
+
+fn frame_target(
+    mut event: EventReader<FrameEvent>,
+    preferences: Res<Preferences>,
+    scene_bounds: Res<SceneBounds>,
+    selected_query: MeshBounds<With<Selected>>,
+    mut camera_query: Query<(&mut CameraController, &mut Projection, &Camera), With<EditorCamera>>,
+) {
+    let preferences = &preferences.camera_control;
+    for FrameEvent(target) in event.read() {
+        let bounds = match target {
+            FrameTarget::All => scene_bounds.0,
+            FrameTarget::Selected => merged_bounds(&selected_query).or(scene_bounds.0),
+        };
+        let Some(bounds) = bounds else {
+            log::warn!("nothing to frame for {target:?}");
+            continue;
+        };
+
+        for (mut controller, mut projection, camera) in camera_query.iter_mut() {
+            controller.frame(
+                bounds,
+                &mut projection,
+                camera.logical_viewport_size(),
+                preferences.min_distance,
+                preferences.max_distance,
+            );
+        }
+    }
+}
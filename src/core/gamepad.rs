@@ -0,0 +1,280 @@
+use bevy::prelude::*;
+
+use crate::preference::Preferences;
+
+use super::{update_camera_controller, RecallBookmarkEvent};
+
+/// Stick and trigger positions of the connected gamepads this frame, with the
+/// dead zone applied. The camera controller and the walkthrough read it.
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct GamepadNavigation {
+    /// Left stick, orbit mode pans and the walkthrough walks. Up is positive.
+    pub movement: Vec2,
+    /// Right stick, orbits or looks around. Up is positive.
+    pub look: Vec2,
+    /// Right trigger minus left trigger, zoom in is positive.
+    pub zoom: f32,
+}
+
+impl GamepadNavigation {
+    pub fn is_idle(&self) -> bool {
+        self.movement == Vec2::ZERO && self.look == Vec2::ZERO && self.zoom == 0.
+    }
+}
+
+pub struct GamepadNavigationPlugin;
+
+impl Plugin for GamepadNavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GamepadNavigation>().add_systems(
+            Update,
+            (read_gamepad_navigation, cycle_bookmarks_with_gamepad)
+                .before(update_camera_controller),
+        );
+    }
+}
+
+/// Rescales `value` so the dead zone maps to zero and the full range stays reachable.
+fn apply_dead_zone(value: Vec2, dead_zone: f32) -> Vec2 {
+    let length = value.length();
+    if length <= dead_zone || dead_zone >= 1. {
+        return Vec2::ZERO;
+    }
+    value / length * ((length - dead_zone) / (1. - dead_zone)).min(1.)
+}
+
+fn read_gamepad_navigation(
+    preferences: Res<Preferences>,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    button_axes: Res<Axis<GamepadButton>>,
+    mut navigation: ResMut<GamepadNavigation>,
+) {
+    let dead_zone = preferences.camera_control.gamepad_dead_zone;
+    let stick = |gamepad, x, y| {
+        let axis = |axis_type| axes.get(GamepadAxis::new(gamepad, axis_type)).unwrap_or(0.);
+        apply_dead_zone(Vec2::new(axis(x), axis(y)), dead_zone)
+    };
+    let trigger = |gamepad, button_type| {
+        let value = button_axes
+            .get(GamepadButton::new(gamepad, button_type))
+            .unwrap_or(0.);
+        if value > dead_zone {
+            value
+        } else {
+            0.
+        }
+    };
+
+    // Several gamepads add up, whichever one is held drives the camera.
+    let mut state = GamepadNavigation::default();
+    for gamepad in gamepads.iter() {
+        use GamepadAxisType::*;
+        use GamepadButtonType::*;
+
+        state.movement += stick(gamepad, LeftStickX, LeftStickY);
+        state.look += stick(gamepad, RightStickX, RightStickY);
+        state.zoom += trigger(gamepad, RightTrigger2) - trigger(gamepad, LeftTrigger2);
+    }
+    state.movement = state.movement.clamp_length_max(1.);
+    state.look = state.look.clamp_length_max(1.);
+    state.zoom = state.zoom.clamp(-1., 1.);
+
+    *navigation = state;
+}
+
+/// Shoulder buttons step through the saved bookmarks, the right one forward.
+fn cycle_bookmarks_with_gamepad(
+    preferences: Res<Preferences>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    mut current: Local<Option<usize>>,
+    mut recall_event: EventWriter<RecallBookmarkEvent>,
+) {
+    let bookmarks = &preferences.camera_bookmarks;
+    if bookmarks.is_empty() {
+        return;
+    }
+
+    let mut step = 0;
+    for gamepad in gamepads.iter() {
+        let just_pressed =
+            |button_type| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, button_type));
+        if just_pressed(GamepadButtonType::RightTrigger) {
+            step += 1;
+        }
+        if just_pressed(GamepadButtonType::LeftTrigger) {
+            step -= 1;
+        }
+    }
+    if step == 0 {
+        return;
+    }
+
+    let count = bookmarks.len() as isize;
+    let index = match *current {
+        Some(index) => (index as isize + step).rem_euclid(count),
+        // The first press goes to the first or the last bookmark.
+        None if step > 0 => 0,
+        None => count - 1,
+    } as usize;
+
+    *current = Some(index);
+    recall_event.send(RecallBookmarkEvent(bookmarks[index].name.clone()));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::input::gamepad::{
+        GamepadAxisChangedEvent, GamepadButtonChangedEvent, GamepadConnection,
+        GamepadConnectionEvent, GamepadEvent, GamepadInfo,
+    };
+    use bevy::input::InputPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use bevy::window::CursorMoved;
+
+    use crate::core::{ActiveViewport, CameraController, FloorLevel, SceneBounds};
+    use crate::input::{ActionState, InputFocus};
+    use crate::preference::CameraBookmark;
+
+    use super::*;
+
+    const GAMEPAD: Gamepad = Gamepad { id: 0 };
+
+    /// Headless app with the gamepad navigation driving a single active camera.
+    fn app(preferences: Preferences) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, InputPlugin, GamepadNavigationPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(16)))
+            .insert_resource(preferences)
+            .init_resource::<ActionState>()
+            .init_resource::<InputFocus>()
+            .init_resource::<SceneBounds>()
+            .init_resource::<FloorLevel>()
+            .add_event::<RecallBookmarkEvent>()
+            .add_event::<CursorMoved>()
+            .add_systems(Update, update_camera_controller);
+        app.world_mut().spawn((
+            // Above the floor, so the limits leave the camera alone.
+            CameraController {
+                distance: 10.,
+                point_of_view: Vec3::new(0., 5., 0.),
+                ..default()
+            },
+            Transform::default(),
+            Projection::default(),
+            Camera::default(),
+            ActiveViewport,
+        ));
+
+        send(
+            &mut app,
+            GamepadEvent::Connection(GamepadConnectionEvent::new(
+                GAMEPAD,
+                GamepadConnection::Connected(GamepadInfo {
+                    name: "Test gamepad".to_owned(),
+                }),
+            )),
+        );
+        app.update();
+        app
+    }
+
+    fn send(app: &mut App, event: GamepadEvent) {
+        app.world_mut().send_event(event);
+    }
+
+    fn move_stick(app: &mut App, axis_type: GamepadAxisType, value: f32) {
+        send(
+            app,
+            GamepadEvent::Axis(GamepadAxisChangedEvent::new(GAMEPAD, axis_type, value)),
+        );
+    }
+
+    /// Presses and releases `button_type` over two frames, returns the recalled bookmarks.
+    fn click(app: &mut App, button_type: GamepadButtonType) -> Vec<String> {
+        let mut recalled = Vec::new();
+        for value in [1., 0.] {
+            send(
+                app,
+                GamepadEvent::Button(GamepadButtonChangedEvent::new(GAMEPAD, button_type, value)),
+            );
+            app.update();
+            recalled.extend(
+                app.world_mut()
+                    .resource_mut::<Events<RecallBookmarkEvent>>()
+                    .drain()
+                    .map(|RecallBookmarkEvent(name)| name),
+            );
+        }
+        recalled
+    }
+
+    fn controller(app: &mut App) -> (f32, f32, Vec3) {
+        let controller = app
+            .world_mut()
+            .query::<&CameraController>()
+            .single(app.world());
+        (controller.yaw, controller.pitch, controller.point_of_view)
+    }
+
+    #[test]
+    fn dead_zone_is_ignored() {
+        let mut app = app(Preferences::default());
+        let before = controller(&mut app);
+
+        move_stick(&mut app, GamepadAxisType::LeftStickY, 0.1);
+        move_stick(&mut app, GamepadAxisType::RightStickX, -0.1);
+        for _ in 0..10 {
+            app.update();
+        }
+
+        assert!(app.world().resource::<GamepadNavigation>().is_idle());
+        assert_eq!(controller(&mut app), before);
+    }
+
+    #[test]
+    fn sticks_move_the_camera() {
+        let mut app = app(Preferences::default());
+        let (yaw, _, point_of_view) = controller(&mut app);
+
+        move_stick(&mut app, GamepadAxisType::LeftStickY, 1.);
+        move_stick(&mut app, GamepadAxisType::RightStickX, 1.);
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let navigation = *app.world().resource::<GamepadNavigation>();
+        assert_eq!(navigation.movement, Vec2::Y);
+        assert_eq!(navigation.look, Vec2::X);
+
+        let (moved_yaw, _, moved_point_of_view) = controller(&mut app);
+        assert!(moved_yaw < yaw, "the right stick turns right");
+        assert!(
+            moved_point_of_view.z < point_of_view.z,
+            "the left stick moves forward"
+        );
+    }
+
+    #[test]
+    fn bumpers_cycle_bookmarks() {
+        let bookmark = |name: &str| CameraBookmark {
+            name: name.to_owned(),
+            yaw: 0.,
+            pitch: 0.,
+            distance: 10.,
+            point_of_view: [0.; 3],
+        };
+        let mut app = app(Preferences {
+            camera_bookmarks: vec![bookmark("Hall"), bookmark("Kitchen"), bookmark("Attic")],
+            ..default()
+        });
+
+        assert_eq!(click(&mut app, GamepadButtonType::LeftTrigger), ["Attic"]);
+        assert_eq!(click(&mut app, GamepadButtonType::RightTrigger), ["Hall"]);
+        assert_eq!(click(&mut app, GamepadButtonType::RightTrigger), ["Kitchen"]);
+        assert_eq!(click(&mut app, GamepadButtonType::LeftTrigger), ["Hall"]);
+    }
+}
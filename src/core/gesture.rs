@@ -0,0 +1,78 @@
+use bevy::ecs::system::SystemParam;
+use bevy::input::gestures::{PanGesture, PinchGesture, RotationGesture};
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::preference::Preferences;
+
+/// Camera steps the touchpad and touchscreen gestures made since the last frame.
+#[derive(Debug, Default, Clone, Copy)]
+pub(super) struct GestureSteps {
+    /// Two-finger scroll in logical pixels the scene should follow.
+    pub pan: Vec2,
+    /// Relative magnification, zoom in is positive.
+    pub pinch: f32,
+    /// Counterclockwise rotation in radians.
+    pub rotation: f32,
+}
+
+/// Reads the gestures, touchpads report their two-finger scroll as pixel wheel
+/// events that pan instead of zoom, the line steps of a mouse wheel are actions.
+/// Pixel scrolls are wheel steps too when the preferences say they don't pan.
+#[derive(SystemParam)]
+pub(super) struct GestureInput<'w, 's> {
+    preferences: Res<'w, Preferences>,
+    mouse_wheel_events: EventReader<'w, 's, MouseWheel>,
+    pan_events: EventReader<'w, 's, PanGesture>,
+    pinch_events: EventReader<'w, 's, PinchGesture>,
+    rotation_events: EventReader<'w, 's, RotationGesture>,
+    touches: Res<'w, Touches>,
+    window_query: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+}
+
+impl GestureInput<'_, '_> {
+    pub fn read(&mut self) -> GestureSteps {
+        let mut steps = GestureSteps::default();
+        // Pixel scrolls and pan gestures come in physical pixels, touches in logical ones.
+        let scale_factor = self
+            .window_query
+            .get_single()
+            .map_or(1., Window::scale_factor);
+
+        let pixel_scroll_pans = self.preferences.camera_control.pixel_scroll_pans;
+        for event in self.mouse_wheel_events.read() {
+            if pixel_scroll_pans && event.unit == MouseScrollUnit::Pixel {
+                steps.pan += Vec2::new(event.x, event.y) / scale_factor;
+            }
+        }
+        for PanGesture(delta) in self.pan_events.read() {
+            steps.pan += *delta / scale_factor;
+        }
+        for PinchGesture(delta) in self.pinch_events.read() {
+            steps.pinch += *delta;
+        }
+        // Touchpads report their rotation in degrees.
+        for RotationGesture(delta) in self.rotation_events.read() {
+            steps.rotation += delta.to_radians();
+        }
+
+        // Two fingers on a touchscreen pan with their midpoint, pinch with
+        // their distance and rotate with the line between them.
+        let mut touches = self.touches.iter();
+        if let (Some(first), Some(second), None) = (touches.next(), touches.next(), touches.next())
+        {
+            let previous = second.previous_position() - first.previous_position();
+            let current = second.position() - first.position();
+
+            steps.pan += (first.delta() + second.delta()) / 2.;
+            if previous.length() > 0. && current.length() > 0. {
+                steps.pinch += current.length() / previous.length() - 1.;
+                // Screen y points down, so the screen angle turns the other way.
+                steps.rotation -= previous.angle_between(current);
+            }
+        }
+
+        steps
+    }
+}
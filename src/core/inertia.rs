@@ -0,0 +1,65 @@
+use std::ops::{Add, Mul, Sub};
+
+use bevy::prelude::*;
+
+/// Time constant in seconds of the velocity estimate taken while the user drags the camera.
+const VELOCITY_SMOOTHING: f32 = 0.05;
+
+/// Speeds below this are treated as standing still.
+const REST_SPEED: f32 = 1e-4;
+
+/// Velocities the camera keeps gliding with after the user lets go.
+#[derive(Default, Debug, Clone, Reflect)]
+pub struct CameraInertia {
+    /// Yaw and pitch change in radians per second.
+    pub rotation: Vec2,
+    /// Point of view movement in world units per second.
+    pub pan: Vec3,
+    /// Zoom speed in natural logarithm of the orbit distance per second.
+    pub zoom: f32,
+}
+
+impl CameraInertia {
+    pub fn stop(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Feeds the step the user made this frame into a smoothed velocity estimate.
+pub(super) fn track<T>(velocity: &mut T, step: T, delta_seconds: f32)
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>,
+{
+    if delta_seconds <= 0. {
+        return;
+    }
+    let alpha = 1. - (-delta_seconds / VELOCITY_SMOOTHING).exp();
+    *velocity = *velocity + (step * (1. / delta_seconds) - *velocity) * alpha;
+}
+
+/// Adds a velocity that glides exactly `distance` before coming to rest with the given damping.
+pub(super) fn impulse<T>(velocity: &mut T, distance: T, damping: f32)
+where
+    T: Copy + Add<Output = T> + Mul<f32, Output = T>,
+{
+    *velocity = *velocity + distance * damping.max(f32::EPSILON);
+}
+
+/// Advances an exponentially damped velocity by `delta_seconds`, returning the step covered.
+///
+/// The decay is integrated exactly, so the glide is the same at any frame rate.
+pub(super) fn glide<T>(velocity: &mut T, speed: f32, damping: f32, delta_seconds: f32) -> T
+where
+    T: Copy + Default + Mul<f32, Output = T>,
+{
+    if speed < REST_SPEED {
+        *velocity = T::default();
+        return T::default();
+    }
+
+    let damping = damping.max(f32::EPSILON);
+    let decay = (-damping * delta_seconds).exp();
+    let step = *velocity * ((1. - decay) / damping);
+    *velocity = *velocity * decay;
+    step
+}
@@ -0,0 +1,83 @@
+use bevy::math::bounding::Aabb3d;
+use bevy::prelude::*;
+
+use crate::preference::CameraControlPreferences;
+use crate::CM;
+
+use super::{CameraController, CameraMode, CameraView};
+
+/// Rate per second at which the camera is pulled back inside a limit it overshot.
+const LIMIT_STIFFNESS: f32 = 12.0;
+
+/// Pulls `value` back into `min..=max` a part of the way each frame instead of clamping it,
+/// so pushing against a limit feels like stretching a rubber band.
+fn soften(value: f32, min: f32, max: f32, delta_seconds: f32) -> f32 {
+    let target = value.clamp(min, max.max(min));
+    target + (value - target) * (-LIMIT_STIFFNESS * delta_seconds).exp()
+}
+
+/// Pitch range in radians the limits allow for an orbit around `point_of_view` at
+/// `distance`: the range of the preferences, narrowed so the camera stays above the floor.
+fn pitch_range(
+    preferences: &CameraControlPreferences,
+    point_of_view: Vec3,
+    distance: f32,
+    floor: f32,
+) -> (f32, f32) {
+    // The camera sits `distance * sin(pitch)` below the point of view.
+    let clearance = point_of_view.y - floor - preferences.min_height_cm * CM;
+    let floor_pitch = (clearance / distance.max(f32::EPSILON))
+        .clamp(-1., 1.)
+        .asin();
+
+    let min_pitch = preferences.min_pitch_degrees.to_radians();
+    let max_pitch = preferences.max_pitch_degrees.to_radians().min(floor_pitch);
+    (min_pitch, max_pitch.max(min_pitch))
+}
+
+impl CameraView {
+    /// Keeps the pitch of a view the user picked inside the limits, so the camera
+    /// doesn't spring away from it once it gets there.
+    pub(super) fn clamp_pitch(&mut self, floor: f32, preferences: &CameraControlPreferences) {
+        if preferences.limits_on {
+            let (min_pitch, max_pitch) =
+                pitch_range(preferences, self.point_of_view, self.distance, floor);
+            self.pitch = self.pitch.clamp(min_pitch, max_pitch);
+        }
+    }
+}
+
+impl CameraController {
+    /// Eases the camera back inside the limits of the preferences: the point of view
+    /// stays around the scene, the orbit keeps its pitch range and the camera stays
+    /// above the floor.
+    pub(super) fn apply_limits(
+        &mut self,
+        scene_bounds: Option<Aabb3d>,
+        floor: f32,
+        preferences: &CameraControlPreferences,
+        delta_seconds: f32,
+    ) {
+        if let Some(bounds) = scene_bounds {
+            let margin = preferences.bounds_margin_cm * CM;
+            let min = Vec3::from(bounds.min) - margin;
+            let max = Vec3::from(bounds.max) + margin;
+
+            let point_of_view = &mut self.point_of_view;
+            point_of_view.x = soften(point_of_view.x, min.x, max.x, delta_seconds);
+            point_of_view.z = soften(point_of_view.z, min.z, max.z, delta_seconds);
+            // The plan view camera height is fixed, it only moves across the plan.
+            if self.mode == CameraMode::Orbit {
+                point_of_view.y = soften(point_of_view.y, floor, max.y, delta_seconds);
+            }
+        }
+
+        if self.mode != CameraMode::Orbit {
+            return;
+        }
+
+        let (min_pitch, max_pitch) =
+            pitch_range(preferences, self.point_of_view, self.distance, floor);
+        self.pitch = soften(self.pitch, min_pitch, max_pitch, delta_seconds);
+    }
+}
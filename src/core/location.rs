@@ -1,13 +1,15 @@
 use bevy::prelude::*;
 
-use super::{CameraControllPlugin, CameraController, EditorCamera};
+use super::{
+    ActiveViewport, CameraControllPlugin, CameraController, EditorCamera, EditorViewport, ScenePlugin,
+};
 
 pub struct LocationPlugin;
 
 impl Plugin for LocationPlugin {
   fn build(&self, app: &mut App) {
     app
-      .add_plugins(CameraControllPlugin)
+      .add_plugins((ScenePlugin, CameraControllPlugin))
       .add_systems(Startup, setup);
   }
 }
@@ -24,6 +26,8 @@ fn setup(
             ..Default::default()
         },
         EditorCamera,
+        EditorViewport::Perspective,
+        ActiveViewport,
         CameraController {
             distance: 90.0,
             yaw: 0.0,
@@ -1,6 +1,34 @@
+mod bookmark;
 mod camera_controll;
+mod clipping;
+mod framing;
+mod gamepad;
+mod gesture;
+mod inertia;
+mod limits;
 mod location;
+mod orbit_pivot;
+mod plan_view;
+mod scene;
+mod standard_view;
+mod transition;
+mod viewport;
+mod walkthrough;
 
 use camera_controll::*;
+use gesture::*;
 
+pub use bookmark::*;
+pub use clipping::*;
+pub use camera_controll::{CameraController, CameraMode, EditorCamera};
+pub use framing::*;
+pub use gamepad::*;
+pub use inertia::*;
 pub use location::*;
+pub use orbit_pivot::*;
+pub use plan_view::*;
+pub use scene::*;
+pub use standard_view::*;
+pub use transition::*;
+pub use viewport::*;
+pub use walkthrough::*;
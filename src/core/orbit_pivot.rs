@@ -0,0 +1,67 @@
+use bevy::color::palettes::css::ORANGE;
+use bevy::prelude::*;
+
+use super::{CameraController, EditorCamera};
+
+/// Size of the pivot marker relative to its distance from the camera.
+const PIVOT_MARKER_SIZE: f32 = 0.01;
+
+pub struct OrbitPivotPlugin;
+
+impl Plugin for OrbitPivotPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostUpdate, draw_orbit_pivot);
+    }
+}
+
+impl CameraController {
+    /// Orbits around `pivot` from now on. The point of view moves along the view axis
+    /// to the depth of the pivot, kept within the zoom limits, so the camera itself
+    /// doesn't move.
+    pub(super) fn set_pivot(
+        &mut self,
+        pivot: Vec3,
+        camera_transform: &Transform,
+        min_distance: f32,
+        max_distance: f32,
+    ) {
+        let forward = camera_transform.forward();
+        let depth = (pivot - camera_transform.translation).dot(*forward);
+
+        if depth > 0. {
+            self.distance = depth.clamp(min_distance, max_distance);
+            self.point_of_view = camera_transform.translation + forward * self.distance;
+        }
+        self.pivot = Some(pivot);
+    }
+
+    /// Turns the camera by a yaw and pitch step, swinging it around the pivot when there is one.
+    pub(super) fn orbit(&mut self, step: Vec2) {
+        let previous_rotation = self.rotation();
+        self.rotate(step);
+
+        if let Some(pivot) = self.pivot {
+            // The same turn the camera makes, applied to the whole rig around the pivot.
+            let turn = self.rotation() * previous_rotation.inverse();
+            self.point_of_view = pivot + turn * (self.point_of_view - pivot);
+        }
+    }
+}
+
+fn draw_orbit_pivot(
+    mut gizmos: Gizmos,
+    query: Query<(&CameraController, &GlobalTransform), With<EditorCamera>>,
+) {
+    for (controller, transform) in query.iter() {
+        let Some(pivot) = controller.pivot else {
+            continue;
+        };
+        if !controller.is_rotating {
+            continue;
+        }
+
+        // Keep the marker the same size on screen.
+        let radius = transform.translation().distance(pivot) * PIVOT_MARKER_SIZE;
+        gizmos.sphere(pivot, Quat::IDENTITY, radius, ORANGE);
+    }
+}
@@ -0,0 +1,108 @@
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::prelude::*;
+use bevy::render::camera::ScalingMode;
+
+use crate::input::{Action, ActionState};
+use crate::preference::Preferences;
+
+use super::{update_camera_controller, ActiveViewport, CameraController, CameraMode};
+
+/// Switches the editor camera between the perspective orbit and the orthographic plan view.
+#[derive(Debug, Event)]
+pub struct TogglePlanViewEvent;
+
+pub struct PlanViewPlugin;
+
+impl Plugin for PlanViewPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<TogglePlanViewEvent>().add_systems(
+            Update,
+            (plan_view_shortcut, toggle_plan_view)
+                .chain()
+                .before(update_camera_controller),
+        );
+    }
+}
+
+impl CameraController {
+    /// World units covered by one logical pixel at distance 1 from the camera
+    /// with the stored perspective projection.
+    pub(super) fn pixel_size(&self, viewport_height: Option<f32>) -> f32 {
+        match viewport_height {
+            Some(height) if height > 0. => 2. * (self.perspective.fov / 2.).tan() / height,
+            _ => 1.,
+        }
+    }
+
+    /// Looks straight down at the point of view with an orthographic projection
+    /// that frames the same area as the current perspective view.
+    pub(super) fn enter_plan_view(&mut self, projection: &mut Projection, viewport_height: Option<f32>) {
+        let Projection::Perspective(perspective) = projection else {
+            return;
+        };
+        self.perspective = perspective.clone();
+
+        self.mode = CameraMode::Plan;
+        self.pitch = -FRAC_PI_2;
+
+        *projection = Projection::Orthographic(OrthographicProjection {
+            scaling_mode: ScalingMode::WindowSize(1.),
+            scale: self.distance * self.pixel_size(viewport_height),
+            ..default()
+        });
+    }
+
+    /// Restores the perspective projection, keeping the focus point and picking
+    /// the distance that frames the same area as the plan view.
+    pub(super) fn leave_plan_view(&mut self, projection: &mut Projection, viewport_height: Option<f32>) {
+        let Projection::Orthographic(orthographic) = projection else {
+            return;
+        };
+        self.distance = orthographic.scale / self.pixel_size(viewport_height);
+
+        self.mode = CameraMode::Orbit;
+        // Closest to the top-down view the orbit allows.
+        self.pitch = -89.9_f32.to_radians();
+
+        *projection = Projection::Perspective(self.perspective.clone());
+    }
+}
+
+fn plan_view_shortcut(
+    action_state: Res<ActionState>,
+    mut event: EventWriter<TogglePlanViewEvent>,
+) {
+    if action_state.just_pressed(Action::TogglePlanView) {
+        event.send(TogglePlanViewEvent);
+    }
+}
+
+fn toggle_plan_view(
+    mut event: EventReader<TogglePlanViewEvent>,
+    preferences: Res<Preferences>,
+    mut query: Query<(&mut CameraController, &mut Projection, &Camera), With<ActiveViewport>>,
+) {
+    let preferences = &preferences.camera_control;
+    for _ in event.read() {
+        for (mut controller, mut projection, camera) in query.iter_mut() {
+            let viewport_height = camera.logical_viewport_size().map(|size| size.y);
+            match controller.mode {
+                CameraMode::Orbit | CameraMode::Walkthrough => {
+                    controller.leave_walkthrough();
+                    controller.enter_plan_view(&mut projection, viewport_height);
+                    // The camera hovers at the far end of the zoom range in the plan view.
+                    if let Projection::Orthographic(orthographic) = projection.as_mut() {
+                        orthographic.far = preferences.max_distance * 2.;
+                    }
+                }
+                CameraMode::Plan => {
+                    controller.leave_plan_view(&mut projection, viewport_height);
+                    controller.distance = controller
+                        .distance
+                        .clamp(preferences.min_distance, preferences.max_distance);
+                }
+            }
+        }
+    }
+}
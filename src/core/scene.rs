@@ -0,0 +1,161 @@
+use bevy::ecs::system::SystemParam;
+use bevy::math::bounding::{Aabb3d, BoundingVolume, RayCast3d};
+use bevy::prelude::*;
+use bevy::render::primitives::Aabb;
+use bevy::render::view::VisibilitySystems;
+use bevy::transform::TransformSystem;
+use bevy::window::PrimaryWindow;
+
+use crate::input::{Action, ActionState};
+
+use super::ActiveViewport;
+
+/// Marks entities the user has selected in the plan.
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+pub struct Selected;
+
+/// Marks the floor meshes, the walkthrough stands on them and the rest of the scene blocks it.
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+pub struct Floor;
+
+/// World-space bounds of every mesh in the scene, refreshed each frame.
+#[derive(Resource, Default, Debug, Clone, Copy)]
+pub struct SceneBounds(pub Option<Aabb3d>);
+
+/// Height of the top of the [`Floor`] meshes, 0 without any.
+#[derive(Resource, Default, Debug, Clone, Copy)]
+pub struct FloorLevel(pub f32);
+
+pub struct ScenePlugin;
+
+impl Plugin for ScenePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Selected>()
+            .register_type::<Floor>()
+            .init_resource::<SceneBounds>()
+            .init_resource::<FloorLevel>()
+            .add_systems(Update, select_on_click)
+            .add_systems(
+                PostUpdate,
+                update_scene_bounds
+                    .after(TransformSystem::TransformPropagate)
+                    .after(VisibilitySystems::CalculateBounds),
+            );
+    }
+}
+
+/// Converts a local mesh [`Aabb`] into a world-space box enclosing the transformed mesh.
+pub fn world_aabb(aabb: &Aabb, transform: &GlobalTransform) -> Aabb3d {
+    let affine = transform.affine();
+    let center = affine.transform_point3a(aabb.center);
+    let matrix = affine.matrix3;
+    let half_size = matrix.x_axis.abs() * aabb.half_extents.x
+        + matrix.y_axis.abs() * aabb.half_extents.y
+        + matrix.z_axis.abs() * aabb.half_extents.z;
+
+    Aabb3d::new(center, half_size)
+}
+
+/// Bounding boxes of the meshes matching the filter `F`, to pass to [`merged_bounds`].
+pub type MeshBounds<'w, 's, F = ()> =
+    Query<'w, 's, (&'static Aabb, &'static GlobalTransform), (With<Handle<Mesh>>, F)>;
+
+/// Merges the world-space boxes of the given meshes, `None` when there are none.
+pub fn merged_bounds<'a>(
+    meshes: impl IntoIterator<Item = (&'a Aabb, &'a GlobalTransform)>,
+) -> Option<Aabb3d> {
+    meshes
+        .into_iter()
+        .map(|(aabb, transform)| world_aabb(aabb, transform))
+        .reduce(|bounds, aabb| bounds.merge(&aabb))
+}
+
+/// Finds the surface point under a ray, using the bounding boxes of the scene meshes
+/// and the floor plane at the [`FloorLevel`] where no mesh is hit.
+#[derive(SystemParam)]
+pub struct SceneRaycast<'w, 's> {
+    meshes: Query<'w, 's, (Entity, &'static Aabb, &'static GlobalTransform), With<Handle<Mesh>>>,
+    floor_level: Res<'w, FloorLevel>,
+}
+
+impl SceneRaycast<'_, '_> {
+    pub fn cast(&self, ray: Ray3d) -> Option<Vec3> {
+        let mesh_hit = self.mesh_hit(ray).map(|(_, distance)| distance);
+        let floor_hit = || {
+            let floor = Vec3::Y * self.floor_level.0;
+            ray.intersect_plane(floor, InfinitePlane3d::new(Vec3::Y))
+        };
+
+        mesh_hit.or_else(floor_hit).map(|distance| ray.get_point(distance))
+    }
+
+    /// Mesh the ray hits first, `None` over the background.
+    pub fn pick(&self, ray: Ray3d) -> Option<Entity> {
+        self.mesh_hit(ray).map(|(entity, _)| entity)
+    }
+
+    /// Nearest mesh along the ray with the distance to its box.
+    fn mesh_hit(&self, ray: Ray3d) -> Option<(Entity, f32)> {
+        let ray_cast = RayCast3d::from_ray(ray, f32::MAX);
+
+        self.meshes
+            .iter()
+            .filter_map(|(entity, aabb, transform)| {
+                let distance = ray_cast.aabb_intersection_at(&world_aabb(aabb, transform))?;
+                Some((entity, distance))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+    }
+}
+
+/// Ray from the camera through a point of its viewport given in logical pixels.
+pub fn viewport_ray(camera: &Camera, transform: &Transform, viewport_position: Vec2) -> Option<Ray3d> {
+    camera.viewport_to_world(&GlobalTransform::from(*transform), viewport_position)
+}
+
+/// Selects the mesh clicked in the active viewport, a click on the background
+/// clears the selection.
+fn select_on_click(
+    mut commands: Commands,
+    action_state: Res<ActionState>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &Transform), With<ActiveViewport>>,
+    scene_raycast: SceneRaycast,
+    selected_query: Query<Entity, With<Selected>>,
+) {
+    if !action_state.just_pressed(Action::Select) {
+        return;
+    }
+    let Ok((camera, transform)) = camera_query.get_single() else {
+        return;
+    };
+    // Cursor in the viewport's own coordinates.
+    let picked = window_query
+        .get_single()
+        .ok()
+        .and_then(Window::cursor_position)
+        .zip(camera.logical_viewport_rect())
+        .and_then(|(cursor, rect)| viewport_ray(camera, transform, cursor - rect.min))
+        .and_then(|ray| scene_raycast.pick(ray));
+
+    for entity in selected_query.iter() {
+        if Some(entity) != picked {
+            commands.entity(entity).remove::<Selected>();
+        }
+    }
+    if let Some(entity) = picked {
+        commands.entity(entity).insert(Selected);
+    }
+}
+
+pub(super) fn update_scene_bounds(
+    mut scene_bounds: ResMut<SceneBounds>,
+    mut floor_level: ResMut<FloorLevel>,
+    query: MeshBounds,
+    floor_query: MeshBounds<With<Floor>>,
+) {
+    scene_bounds.0 = merged_bounds(&query);
+    floor_level.0 = merged_bounds(&floor_query).map_or(0., |bounds| bounds.max.y);
+}
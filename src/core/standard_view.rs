@@ -0,0 +1,208 @@
+use std::f32::consts::{FRAC_1_SQRT_2, FRAC_PI_2, FRAC_PI_4, PI};
+
+use bevy::prelude::*;
+use strum::IntoEnumIterator;
+use strum_macros::{AsRefStr, EnumIter};
+
+use crate::input::{Action, ActionState};
+use crate::preference::Preferences;
+
+use super::{update_camera_controller, ActiveViewport, CameraController, CameraMode, FloorLevel};
+
+/// Steepest pitch the orbit allows, looking straight up or down has no valid up vector.
+const MAX_PITCH: f32 = 89.9 * PI / 180.;
+
+/// Axis-aligned and isometric orientations around the point of view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, AsRefStr)]
+pub enum StandardView {
+    Top,
+    Bottom,
+    Front,
+    Back,
+    Left,
+    Right,
+    Isometric,
+}
+
+impl StandardView {
+    /// Yaw and pitch of the camera looking from this side.
+    pub fn angles(&self) -> Vec2 {
+        match self {
+            Self::Top => Vec2::new(0., -MAX_PITCH),
+            Self::Bottom => Vec2::new(0., MAX_PITCH),
+            Self::Front => Vec2::new(0., 0.),
+            Self::Back => Vec2::new(PI, 0.),
+            Self::Left => Vec2::new(-FRAC_PI_2, 0.),
+            Self::Right => Vec2::new(FRAC_PI_2, 0.),
+            // Looking down the diagonal of a cube from its front right top corner.
+            Self::Isometric => Vec2::new(FRAC_PI_4, -FRAC_1_SQRT_2.atan()),
+        }
+    }
+
+    /// Action switching to this view.
+    fn action(&self) -> Action {
+        match self {
+            Self::Top => Action::ViewTop,
+            Self::Bottom => Action::ViewBottom,
+            Self::Front => Action::ViewFront,
+            Self::Back => Action::ViewBack,
+            Self::Right => Action::ViewRight,
+            Self::Left => Action::ViewLeft,
+            Self::Isometric => Action::ViewIsometric,
+        }
+    }
+}
+
+/// Yaw and pitch of the camera looking at the point of view from `direction`.
+pub fn angles_from_direction(direction: Vec3) -> Vec2 {
+    let direction = direction.normalize_or_zero();
+    let pitch = (-direction.y).asin().clamp(-MAX_PITCH, MAX_PITCH);
+    // Straight above or below any yaw works, keep the plan upright.
+    let yaw = if direction.x == 0. && direction.z == 0. {
+        0.
+    } else {
+        direction.x.atan2(direction.z)
+    };
+    Vec2::new(yaw, pitch)
+}
+
+/// Animates the editor camera to a standard view around its point of view.
+#[derive(Debug, Event)]
+pub struct StandardViewEvent(pub StandardView);
+
+/// Animates the editor camera to look at its point of view from the given direction.
+#[derive(Debug, Event)]
+pub struct LookFromEvent(pub Vec3);
+
+/// Orbits the editor camera by a yaw and pitch step in radians.
+#[derive(Debug, Event)]
+pub struct OrbitViewEvent(pub Vec2);
+
+/// Turns the editor camera around its point of view by the given number of
+/// turn steps, positive steps move the camera right and up.
+#[derive(Debug, Event)]
+pub struct TurnViewEvent(pub IVec2);
+
+pub struct StandardViewPlugin;
+
+impl Plugin for StandardViewPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<StandardViewEvent>()
+            .add_event::<LookFromEvent>()
+            .add_event::<OrbitViewEvent>()
+            .add_event::<TurnViewEvent>()
+            .add_systems(
+                Update,
+                (
+                    standard_view_shortcut,
+                    (set_standard_view, look_from, orbit_view, turn_view),
+                )
+                    .chain()
+                    .before(update_camera_controller),
+            );
+    }
+}
+
+fn standard_view_shortcut(
+    action_state: Res<ActionState>,
+    mut view_event: EventWriter<StandardViewEvent>,
+    mut turn_event: EventWriter<TurnViewEvent>,
+    camera_query: Query<&CameraController, With<ActiveViewport>>,
+) {
+    for view in StandardView::iter() {
+        if action_state.just_pressed(view.action()) {
+            view_event.send(StandardViewEvent(view));
+        }
+    }
+
+    // The arrows walk in the walkthrough, holding Ctrl with them doesn't turn it
+    // into an orbit.
+    let walking = camera_query
+        .iter()
+        .any(|controller| controller.mode == CameraMode::Walkthrough);
+    if walking {
+        return;
+    }
+
+    for (action, steps) in [
+        (Action::TurnLeft, IVec2::NEG_X),
+        (Action::TurnRight, IVec2::X),
+        (Action::TurnUp, IVec2::Y),
+        (Action::TurnDown, IVec2::NEG_Y),
+    ] {
+        if action_state.just_pressed(action) {
+            turn_event.send(TurnViewEvent(steps));
+        }
+    }
+}
+
+fn set_standard_view(
+    mut event: EventReader<StandardViewEvent>,
+    preferences: Res<Preferences>,
+    floor_level: Res<FloorLevel>,
+    mut query: Query<&mut CameraController, With<ActiveViewport>>,
+) {
+    let preferences = &preferences.camera_control;
+    for StandardViewEvent(view) in event.read() {
+        let angles = view.angles();
+        for mut controller in query.iter_mut() {
+            let mut target = controller.target_view();
+            target.yaw = angles.x;
+            target.pitch = angles.y;
+            target.clamp_pitch(floor_level.0, preferences);
+            controller.transition_to(target, preferences.transition_duration);
+        }
+    }
+}
+
+fn look_from(
+    mut event: EventReader<LookFromEvent>,
+    preferences: Res<Preferences>,
+    floor_level: Res<FloorLevel>,
+    mut query: Query<&mut CameraController, With<ActiveViewport>>,
+) {
+    let preferences = &preferences.camera_control;
+    for LookFromEvent(direction) in event.read() {
+        let angles = angles_from_direction(*direction);
+        for mut controller in query.iter_mut() {
+            let mut target = controller.target_view();
+            target.yaw = angles.x;
+            target.pitch = angles.y;
+            target.clamp_pitch(floor_level.0, preferences);
+            controller.transition_to(target, preferences.transition_duration);
+        }
+    }
+}
+
+fn orbit_view(
+    mut event: EventReader<OrbitViewEvent>,
+    mut query: Query<&mut CameraController, With<ActiveViewport>>,
+) {
+    for OrbitViewEvent(step) in event.read() {
+        for mut controller in query.iter_mut() {
+            controller.cancel_transition();
+            controller.orbit(*step);
+        }
+    }
+}
+
+fn turn_view(
+    mut event: EventReader<TurnViewEvent>,
+    preferences: Res<Preferences>,
+    floor_level: Res<FloorLevel>,
+    mut query: Query<&mut CameraController, With<ActiveViewport>>,
+) {
+    let preferences = &preferences.camera_control;
+    let step = preferences.turn_step_degrees.to_radians();
+
+    for TurnViewEvent(steps) in event.read() {
+        for mut controller in query.iter_mut() {
+            // Keep turning from where a running turn is heading, so quick presses add up.
+            let mut target = controller.target_view();
+            target.yaw += steps.x as f32 * step;
+            target.pitch = (target.pitch - steps.y as f32 * step).clamp(-MAX_PITCH, MAX_PITCH);
+            target.clamp_pitch(floor_level.0, preferences);
+            controller.transition_to(target, preferences.transition_duration / 2.);
+        }
+    }
+}
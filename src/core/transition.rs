@@ -0,0 +1,131 @@
+use std::f32::consts::{PI, TAU};
+
+use bevy::prelude::*;
+
+use super::{update_camera_controller, CameraController, CameraMode};
+
+/// The part of the [`CameraController`] state that describes what the camera looks at.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Reflect)]
+pub struct CameraView {
+    pub yaw: f32,
+    pub pitch: f32,
+    pub distance: f32,
+    pub point_of_view: Vec3,
+}
+
+impl CameraView {
+    /// Interpolates towards `other`, turning the yaw the short way around.
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        let yaw_delta = (other.yaw - self.yaw + PI).rem_euclid(TAU) - PI;
+
+        Self {
+            yaw: self.yaw + yaw_delta * t,
+            pitch: self.pitch + (other.pitch - self.pitch) * t,
+            distance: self.distance + (other.distance - self.distance) * t,
+            point_of_view: self.point_of_view.lerp(other.point_of_view, t),
+        }
+    }
+}
+
+/// Animation of the [`CameraController`] from one view to another.
+#[derive(Debug, Default, Clone, Reflect)]
+pub struct CameraTransition {
+    from: CameraView,
+    to: CameraView,
+    elapsed: f32,
+    duration: f32,
+}
+
+impl CameraTransition {
+    fn progress(&self) -> f32 {
+        if self.duration <= 0. {
+            1.
+        } else {
+            (self.elapsed / self.duration).min(1.)
+        }
+    }
+}
+
+/// Cubic ease-in-out, starts and stops smoothly.
+fn ease_in_out(t: f32) -> f32 {
+    if t < 0.5 {
+        4. * t * t * t
+    } else {
+        1. - (-2. * t + 2.).powi(3) / 2.
+    }
+}
+
+pub struct CameraTransitionPlugin;
+
+impl Plugin for CameraTransitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, animate_camera_transition.before(update_camera_controller));
+    }
+}
+
+impl CameraController {
+    pub fn view(&self) -> CameraView {
+        CameraView {
+            yaw: self.yaw,
+            pitch: self.pitch,
+            distance: self.distance,
+            point_of_view: self.point_of_view,
+        }
+    }
+
+    pub fn set_view(&mut self, view: CameraView) {
+        self.yaw = view.yaw;
+        self.pitch = view.pitch;
+        self.distance = view.distance;
+        self.point_of_view = view.point_of_view;
+    }
+
+    /// The view a running transition ends in, the current view otherwise.
+    pub fn target_view(&self) -> CameraView {
+        self.transition
+            .as_ref()
+            .map_or_else(|| self.view(), |transition| transition.to)
+    }
+
+    /// Starts animating towards `view`, replacing any running transition.
+    pub fn transition_to(&mut self, mut view: CameraView, duration: f32) {
+        self.leave_walkthrough();
+        let from = self.view();
+        // The plan view always looks straight down.
+        if self.mode == CameraMode::Plan {
+            view.pitch = from.pitch;
+        }
+
+        self.inertia.stop();
+        self.transition = Some(CameraTransition {
+            from,
+            to: view,
+            elapsed: 0.,
+            duration,
+        });
+    }
+
+    /// Stops a running transition where it is.
+    pub fn cancel_transition(&mut self) {
+        self.transition = None;
+    }
+}
+
+fn animate_camera_transition(time: Res<Time>, mut query: Query<&mut CameraController>) {
+    for mut controller in query.iter_mut() {
+        let Some(transition) = controller.transition.as_mut() else {
+            continue;
+        };
+
+        transition.elapsed += time.delta_seconds();
+        let progress = transition.progress();
+        let view = transition
+            .from
+            .lerp(&transition.to, ease_in_out(progress));
+
+        controller.set_view(view);
+        if progress >= 1. {
+            controller.cancel_transition();
+        }
+    }
+}
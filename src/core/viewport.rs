@@ -0,0 +1,233 @@
+use bevy::prelude::*;
+use bevy::render::camera::Viewport;
+use bevy::window::PrimaryWindow;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::input::InputFocus;
+use crate::preference::{Preferences, ViewportLayout};
+
+use super::{update_camera_controller, CameraController, EditorCamera, StandardView};
+
+/// Which part of a split layout an editor camera renders.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect, EnumIter)]
+#[reflect(Component)]
+pub enum EditorViewport {
+    Perspective,
+    Top,
+    Front,
+    Side,
+}
+
+/// Marks the editor camera that gets the user input, the one under the cursor.
+#[derive(Component, Default)]
+pub struct ActiveViewport;
+
+impl EditorViewport {
+    /// Part of the window the viewport covers in the layout, as fractions of the window size.
+    /// `None` when the layout doesn't show it.
+    pub fn area(&self, layout: ViewportLayout) -> Option<Rect> {
+        use EditorViewport::*;
+        use ViewportLayout::*;
+
+        match (layout, self) {
+            (Single, Perspective) => Some(Rect::new(0., 0., 1., 1.)),
+            (Two, Top) => Some(Rect::new(0., 0., 0.5, 1.)),
+            (Two, Perspective) => Some(Rect::new(0.5, 0., 1., 1.)),
+            (Quad, Top) => Some(Rect::new(0., 0., 0.5, 0.5)),
+            (Quad, Perspective) => Some(Rect::new(0.5, 0., 1., 0.5)),
+            (Quad, Front) => Some(Rect::new(0., 0.5, 0.5, 1.)),
+            (Quad, Side) => Some(Rect::new(0.5, 0.5, 1., 1.)),
+            _ => None,
+        }
+    }
+
+    /// Standard view the viewport starts with, `None` for the free perspective view.
+    fn standard_view(&self) -> Option<StandardView> {
+        match self {
+            Self::Perspective => None,
+            Self::Top => Some(StandardView::Top),
+            Self::Front => Some(StandardView::Front),
+            Self::Side => Some(StandardView::Right),
+        }
+    }
+
+    /// Render order, cameras drawing into the same window need distinct ones.
+    fn order(&self) -> isize {
+        *self as isize
+    }
+}
+
+pub struct ViewportPlugin;
+
+impl Plugin for ViewportPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<EditorViewport>().add_systems(
+            Update,
+            (sync_viewports, update_viewport_rects, update_active_viewport)
+                .chain()
+                .before(update_camera_controller),
+        );
+    }
+}
+
+/// Spawns the cameras the chosen layout needs and despawns the rest.
+/// The perspective viewport is always kept.
+fn sync_viewports(
+    mut commands: Commands,
+    preferences: Res<Preferences>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    query: Query<(Entity, &EditorViewport, &CameraController)>,
+) {
+    let layout = preferences.viewport_layout;
+    let Some(template) = query
+        .iter()
+        .find(|(_, viewport, _)| **viewport == EditorViewport::Perspective)
+        .map(|(_, _, controller)| controller.view())
+    else {
+        return;
+    };
+    let window_height = window_query.get_single().map_or(0., |window| window.height());
+
+    for viewport in EditorViewport::iter() {
+        let area = viewport.area(layout);
+        let existing = query.iter().find(|(_, v, _)| **v == viewport);
+
+        match (area, existing) {
+            (None, Some((entity, _, _))) if viewport != EditorViewport::Perspective => {
+                commands.entity(entity).despawn_recursive();
+            }
+            (Some(area), None) => {
+                let mut projection = Projection::Perspective(default());
+                let mut controller = CameraController {
+                    distance: template.distance,
+                    point_of_view: template.point_of_view,
+                    ..default()
+                };
+                if let Some(angles) = viewport.standard_view().map(|view| view.angles()) {
+                    controller.yaw = angles.x;
+                    controller.pitch = angles.y;
+                }
+                if viewport == EditorViewport::Top {
+                    controller.enter_plan_view(&mut projection, Some(window_height * area.height()));
+                    // The camera hovers at the far end of the zoom range in the plan view.
+                    if let Projection::Orthographic(orthographic) = &mut projection {
+                        orthographic.far = preferences.camera_control.max_distance * 2.;
+                    }
+                }
+
+                commands.spawn((
+                    Camera3dBundle {
+                        camera: Camera {
+                            order: viewport.order(),
+                            ..default()
+                        },
+                        projection,
+                        ..default()
+                    },
+                    EditorCamera,
+                    viewport,
+                    controller,
+                ));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Fits every camera to its part of the window.
+fn update_viewport_rects(
+    preferences: Res<Preferences>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut query: Query<(&EditorViewport, &mut Camera)>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let window_size = window.physical_size().as_vec2();
+
+    for (viewport, mut camera) in query.iter_mut() {
+        let new_viewport = match viewport.area(preferences.viewport_layout) {
+            Some(area) if area != Rect::new(0., 0., 1., 1.) => {
+                let min = (area.min * window_size).as_uvec2();
+                let max = (area.max * window_size).as_uvec2();
+                Some(Viewport {
+                    physical_position: min,
+                    physical_size: (max - min).max(UVec2::ONE),
+                    ..default()
+                })
+            }
+            _ => None,
+        };
+
+        let changed = match (&camera.viewport, &new_viewport) {
+            (Some(old), Some(new)) => {
+                old.physical_position != new.physical_position
+                    || old.physical_size != new.physical_size
+            }
+            (None, None) => false,
+            _ => true,
+        };
+        if changed {
+            camera.viewport = new_viewport;
+        }
+    }
+}
+
+/// Hands the input to the viewport under the cursor. A viewport keeps it
+/// while any mouse button is held, so drags can leave its area, and while
+/// the pointer is over the egui windows above the viewports.
+fn update_active_viewport(
+    mut commands: Commands,
+    preferences: Res<Preferences>,
+    input_focus: Res<InputFocus>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    query: Query<(Entity, &EditorViewport, Has<ActiveViewport>)>,
+) {
+    let layout = preferences.viewport_layout;
+
+    let active = query.iter().find(|(_, _, active)| *active);
+    if let Some((entity, viewport, _)) = active {
+        if viewport.area(layout).is_none() {
+            // The viewport went away with the layout, the perspective view is always there.
+            commands.entity(entity).remove::<ActiveViewport>();
+            if let Some((perspective, _, _)) = query
+                .iter()
+                .find(|(_, viewport, _)| **viewport == EditorViewport::Perspective)
+            {
+                commands.entity(perspective).insert(ActiveViewport);
+            }
+            return;
+        } else if mouse_button_input.get_pressed().next().is_some() {
+            return;
+        }
+    }
+    if !input_focus.scene_has_pointer() {
+        return;
+    }
+
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let Some(cursor) = window.cursor_position() else {
+        return;
+    };
+    let cursor = cursor / window.size();
+
+    let hovered = query.iter().find(|(_, viewport, _)| {
+        viewport
+            .area(layout)
+            .map_or(false, |area| area.contains(cursor))
+    });
+    let Some((hovered, _, false)) = hovered else {
+        return;
+    };
+
+    for (entity, _, active) in query.iter() {
+        if active {
+            commands.entity(entity).remove::<ActiveViewport>();
+        }
+    }
+    commands.entity(hovered).insert(ActiveViewport);
+}
@@ -0,0 +1,259 @@
+use bevy::ecs::system::SystemParam;
+use bevy::input::mouse::MouseMotion;
+use bevy::math::bounding::Aabb3d;
+use bevy::prelude::*;
+
+use crate::input::{Action, ActionState};
+use crate::preference::Preferences;
+use crate::CM;
+
+use super::{
+    update_camera_controller, world_aabb, ActiveViewport, CameraController, CameraMode, Floor,
+    FloorLevel, GamepadNavigation, MeshBounds,
+};
+
+/// Falling acceleration in centimeters per second squared.
+const GRAVITY_CM: f32 = 981.;
+/// Radius of the walker's body in centimeters, walls are kept this far from the eye.
+const BODY_RADIUS_CM: f32 = 25.;
+/// Obstacles lower than this in centimeters are stepped over instead of blocking.
+const STEP_HEIGHT_CM: f32 = 20.;
+/// How many times the body is pushed out of obstacles per frame.
+const COLLISION_ITERATIONS: usize = 3;
+
+/// Enters the walkthrough from any other mode or returns to the orbit.
+#[derive(Debug, Event)]
+pub struct ToggleWalkthroughEvent;
+
+/// Where the walker is in the walkthrough.
+#[derive(Debug, Default, Clone, Reflect)]
+pub struct Walk {
+    /// Position of the eye, the feet are the eye height below it.
+    pub eye: Vec3,
+    /// Downward speed while falling onto the floor.
+    pub fall_speed: f32,
+}
+
+pub struct WalkthroughPlugin;
+
+impl Plugin for WalkthroughPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ToggleWalkthroughEvent>()
+            .add_systems(
+                Update,
+                (walkthrough_shortcut, toggle_walkthrough)
+                    .chain()
+                    .before(update_camera_controller),
+            )
+            .add_systems(Update, update_walkthrough.after(update_camera_controller));
+    }
+}
+
+impl CameraController {
+    /// Puts the eye where the camera currently is, looking the same way.
+    pub(super) fn enter_walkthrough(&mut self, camera_position: Vec3) {
+        self.mode = CameraMode::Walkthrough;
+        self.walk = Walk {
+            eye: camera_position,
+            fall_speed: 0.,
+        };
+    }
+
+    /// Returns to the orbit around a point in front of the eye, keeping the camera in place.
+    pub(super) fn leave_walkthrough(&mut self) {
+        if self.mode != CameraMode::Walkthrough {
+            return;
+        }
+        let forward = self.rotation() * Vec3::NEG_Z;
+
+        self.mode = CameraMode::Orbit;
+        self.point_of_view = self.walk.eye + forward * self.distance;
+    }
+}
+
+fn walkthrough_shortcut(
+    action_state: Res<ActionState>,
+    mut event: EventWriter<ToggleWalkthroughEvent>,
+) {
+    if action_state.just_pressed(Action::ToggleWalkthrough) {
+        event.send(ToggleWalkthroughEvent);
+    }
+}
+
+fn toggle_walkthrough(
+    mut event: EventReader<ToggleWalkthroughEvent>,
+    preferences: Res<Preferences>,
+    mut query: Query<
+        (&mut CameraController, &mut Projection, &Camera, &Transform),
+        With<ActiveViewport>,
+    >,
+) {
+    let eye_height = preferences.camera_control.eye_height_cm * CM;
+    for _ in event.read() {
+        for (mut controller, mut projection, camera, transform) in query.iter_mut() {
+            match controller.mode {
+                CameraMode::Walkthrough => controller.leave_walkthrough(),
+                CameraMode::Plan => {
+                    let viewport_height = camera.logical_viewport_size().map(|size| size.y);
+                    controller.leave_plan_view(&mut projection, viewport_height);
+                    // Stand up on the focused spot instead of falling from the plan view
+                    // camera, looking ahead rather than down at the feet.
+                    let camera_position = controller.point_of_view + Vec3::Y * eye_height;
+                    controller.pitch = 0.;
+                    controller.enter_walkthrough(camera_position);
+                }
+                CameraMode::Orbit => controller.enter_walkthrough(transform.translation),
+            }
+        }
+    }
+}
+
+/// Input the walker reads: the walk and look actions, the mouse and the gamepad.
+#[derive(SystemParam)]
+struct WalkInput<'w, 's> {
+    mouse_motion_events: EventReader<'w, 's, MouseMotion>,
+    action_state: Res<'w, ActionState>,
+    gamepad: Res<'w, GamepadNavigation>,
+}
+
+/// Moves the walker with the walk actions or the left stick, looks around with
+/// a camera drag or the right stick, pulls it onto the floor and keeps it out
+/// of walls and furniture.
+fn update_walkthrough(
+    input: WalkInput,
+    preferences: Res<Preferences>,
+    time: Res<Time>,
+    mut camera_query: Query<(&mut CameraController, &mut Transform), With<ActiveViewport>>,
+    floor_level: Res<FloorLevel>,
+    obstacle_query: MeshBounds<Without<Floor>>,
+) {
+    let WalkInput {
+        mut mouse_motion_events,
+        action_state,
+        gamepad,
+    } = input;
+    let preferences = &preferences.camera_control;
+    let delta_seconds = time.delta_seconds();
+
+    let mut delta = Vec2::ZERO;
+    for event in mouse_motion_events.read() {
+        delta += event.delta;
+    }
+
+    let mut direction = Vec2::ZERO;
+    for (action, step) in [
+        (Action::WalkForward, Vec2::Y),
+        (Action::WalkBackward, Vec2::NEG_Y),
+        (Action::WalkRight, Vec2::X),
+        (Action::WalkLeft, Vec2::NEG_X),
+    ] {
+        if action_state.pressed(action) {
+            direction += step;
+        }
+    }
+    let run = action_state.pressed(Action::Run);
+    // Keys walk at full speed, the stick as far as it is pushed.
+    let direction = (direction.normalize_or_zero() + gamepad.movement).clamp_length_max(1.);
+    // Any camera drag looks around, a modifier like Shift runs here and would
+    // turn an orbit into a pan in some navigation presets.
+    let looking =
+        action_state.pressed(Action::CameraOrbit) || action_state.pressed(Action::CameraPan);
+
+    let eye_height = preferences.eye_height_cm * CM;
+    let floor = floor_level.0;
+    let obstacles: Vec<Aabb3d> = obstacle_query
+        .iter()
+        .map(|(aabb, transform)| world_aabb(aabb, transform))
+        .collect();
+
+    for (mut controller, mut transform) in camera_query.iter_mut() {
+        if controller.mode != CameraMode::Walkthrough {
+            continue;
+        }
+
+        if looking {
+            controller.yaw -= delta.x * preferences.rotation_sensitivity;
+            controller.pitch -= delta.y * preferences.rotation_sensitivity;
+            controller.pitch = controller
+                .pitch
+                .clamp(-89.9_f32.to_radians(), 89.9_f32.to_radians());
+        }
+        let look_step = gamepad.look * preferences.gamepad_look_sensitivity * delta_seconds;
+        controller.yaw -= look_step.x;
+        controller.pitch =
+            (controller.pitch + look_step.y).clamp(-89.9_f32.to_radians(), 89.9_f32.to_radians());
+
+        // Walk on the floor plane whichever way the eye looks.
+        let heading = Quat::from_axis_angle(Vec3::Y, controller.yaw);
+        let speed = preferences.walk_speed_cm * CM * if run { 2. } else { 1. };
+        let step = heading * Vec3::new(direction.x, 0., -direction.y) * speed * delta_seconds;
+
+        let walk = &mut controller.walk;
+        let mut feet = walk.eye - Vec3::Y * eye_height + step;
+
+        walk.fall_speed += GRAVITY_CM * CM * delta_seconds;
+        feet.y -= walk.fall_speed * delta_seconds;
+        if feet.y <= floor {
+            feet.y = floor;
+            walk.fall_speed = 0.;
+        }
+
+        walk.eye = resolve_collisions(feet, eye_height, &obstacles) + Vec3::Y * eye_height;
+
+        transform.translation = controller.walk.eye;
+        transform.rotation = controller.rotation();
+    }
+}
+
+/// Pushes a vertical body standing at `feet` horizontally out of the obstacles it overlaps.
+fn resolve_collisions(mut feet: Vec3, eye_height: f32, obstacles: &[Aabb3d]) -> Vec3 {
+    let radius = BODY_RADIUS_CM * CM;
+    let bottom = feet.y + STEP_HEIGHT_CM * CM;
+    let top = feet.y + eye_height;
+
+    for _ in 0..COLLISION_ITERATIONS {
+        let mut collided = false;
+
+        for obstacle in obstacles {
+            if obstacle.max.y <= bottom || obstacle.min.y >= top {
+                continue;
+            }
+
+            let position = Vec2::new(feet.x, feet.z);
+            let min = Vec2::new(obstacle.min.x, obstacle.min.z);
+            let max = Vec2::new(obstacle.max.x, obstacle.max.z);
+            let closest = position.clamp(min, max);
+            let offset = position - closest;
+            let distance = offset.length();
+
+            let push = if distance > 0. {
+                if distance >= radius {
+                    continue;
+                }
+                offset / distance * (radius - distance)
+            } else {
+                // Inside the footprint, leave through the nearest side.
+                let exits = [
+                    Vec2::new(min.x - radius - position.x, 0.),
+                    Vec2::new(max.x + radius - position.x, 0.),
+                    Vec2::new(0., min.y - radius - position.y),
+                    Vec2::new(0., max.y + radius - position.y),
+                ];
+                exits
+                    .into_iter()
+                    .min_by(|a, b| a.length().total_cmp(&b.length()))
+                    .unwrap_or_default()
+            };
+
+            feet.x += push.x;
+            feet.z += push.y;
+            collided = true;
+        }
+
+        if !collided {
+            break;
+        }
+    }
+
+    feet
+}
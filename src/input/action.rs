@@ -0,0 +1,497 @@
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+
+use crate::preference::{NavigationPreset, Preferences};
+
+use super::{focus::update_input_focus, ButtonGestureSystem, ExtendedButtonInput, InputFocus};
+
+/// Something the user can do, bound to inputs in the preferences.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Orbits the camera while held, looks around in the walkthrough.
+    CameraOrbit,
+    /// Pans the camera while held.
+    CameraPan,
+    /// Zooms with a vertical drag while held.
+    CameraZoom,
+    ZoomIn,
+    ZoomOut,
+    OpenContextMenu,
+    TogglePlanView,
+    ToggleWalkthrough,
+    WalkForward,
+    WalkBackward,
+    WalkLeft,
+    WalkRight,
+    Run,
+    FrameAll,
+    FrameSelected,
+    /// Selects the mesh under the cursor, clears the selection over the background.
+    Select,
+    ViewTop,
+    ViewBottom,
+    ViewFront,
+    ViewBack,
+    ViewLeft,
+    ViewRight,
+    ViewIsometric,
+    TurnLeft,
+    TurnRight,
+    TurnUp,
+    TurnDown,
+    /// Recalls the quick bookmark "View N".
+    RecallBookmark(u8),
+    /// Saves the quick bookmark "View N".
+    SaveBookmark(u8),
+}
+
+impl Action {
+    /// Every action, the quick bookmarks from 1 to 9.
+    pub fn all() -> impl Iterator<Item = Action> {
+        use Action::*;
+
+        [
+            CameraOrbit,
+            CameraPan,
+            CameraZoom,
+            ZoomIn,
+            ZoomOut,
+            OpenContextMenu,
+            TogglePlanView,
+            ToggleWalkthrough,
+            WalkForward,
+            WalkBackward,
+            WalkLeft,
+            WalkRight,
+            Run,
+            FrameAll,
+            FrameSelected,
+            Select,
+            ViewTop,
+            ViewBottom,
+            ViewFront,
+            ViewBack,
+            ViewLeft,
+            ViewRight,
+            ViewIsometric,
+            TurnLeft,
+            TurnRight,
+            TurnUp,
+            TurnDown,
+        ]
+        .into_iter()
+        .chain((1..=9).map(RecallBookmark))
+        .chain((1..=9).map(SaveBookmark))
+    }
+
+    /// Whether the navigation preset binds the action, the camera drags, rather
+    /// than the [`InputBindings`].
+    pub fn follows_preset(&self) -> bool {
+        matches!(self, Self::CameraOrbit | Self::CameraPan | Self::CameraZoom)
+    }
+}
+
+/// Modifier keys a [`Binding`] needs.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(default)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+}
+
+impl Modifiers {
+    pub const NONE: Self = Self {
+        shift: false,
+        ctrl: false,
+        alt: false,
+    };
+    pub const SHIFT: Self = Self {
+        shift: true,
+        ..Self::NONE
+    };
+    pub const CTRL: Self = Self {
+        ctrl: true,
+        ..Self::NONE
+    };
+
+    fn pressed(keyboard_input: &ButtonInput<KeyCode>) -> Self {
+        Self {
+            shift: keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]),
+            ctrl: keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]),
+            alt: keyboard_input.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]),
+        }
+    }
+
+    /// Whether every key of `self` is in `other`.
+    fn within(&self, other: Self) -> bool {
+        (!self.shift || other.shift) && (!self.ctrl || other.ctrl) && (!self.alt || other.alt)
+    }
+}
+
+/// Direction the mouse wheel turns.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WheelDirection {
+    Up,
+    Down,
+}
+
+/// Input that sets off a [`Binding`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputTrigger {
+    /// Held as long as the key is.
+    Key(KeyCode),
+    /// Held while the mouse button drags, from the moment the cursor moves past the
+    /// drag threshold, so a click doesn't nudge the camera.
+    Mouse(MouseButton),
+    /// A short press and release of the mouse button.
+    Click(MouseButton),
+    /// Two clicks in a quick succession.
+    DoubleClick(MouseButton),
+    /// The mouse button held still for a while.
+    LongPress(MouseButton),
+    /// Wheel steps, the action value counts them.
+    Wheel(WheelDirection),
+}
+
+impl InputTrigger {
+    fn is_pointer(&self) -> bool {
+        !matches!(self, Self::Key(_))
+    }
+}
+
+/// InputTrigger together with the modifier keys held with it.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Binding {
+    pub trigger: InputTrigger,
+    #[serde(default)]
+    pub modifiers: Modifiers,
+}
+
+impl Binding {
+    pub const fn new(trigger: InputTrigger, modifiers: Modifiers) -> Self {
+        Self { trigger, modifiers }
+    }
+
+    pub const fn key(key: KeyCode) -> Self {
+        Self::new(InputTrigger::Key(key), Modifiers::NONE)
+    }
+}
+
+/// Bindings of one action.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ActionBindings {
+    pub action: Action,
+    pub bindings: Vec<Binding>,
+}
+
+/// Inputs of every action but the camera drags, those follow the navigation preset.
+/// An action missing from the preferences file gets its default bindings, so new
+/// actions work with old files.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(transparent)]
+pub struct InputBindings(pub Vec<ActionBindings>);
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        let mut bindings = Self(Vec::new());
+        bindings.fill_missing();
+        bindings
+    }
+}
+
+impl InputBindings {
+    /// Binding of `action`, the drags of `preset` for the camera drags and the
+    /// default when it has none.
+    pub fn get(&self, action: Action, preset: NavigationPreset) -> Vec<Binding> {
+        if action.follows_preset() {
+            return preset_bindings(action, preset);
+        }
+        self.0
+            .iter()
+            .find(|entry| entry.action == action)
+            .map_or_else(|| default_bindings(action), |entry| entry.bindings.clone())
+    }
+
+    /// Gives the actions without an entry their default bindings and drops the
+    /// camera drags earlier files stored along with their preset.
+    pub fn fill_missing(&mut self) {
+        self.0.retain(|entry| !entry.action.follows_preset());
+        for action in Action::all().filter(|action| !action.follows_preset()) {
+            if !self.0.iter().any(|entry| entry.action == action) {
+                self.0.push(ActionBindings {
+                    action,
+                    bindings: default_bindings(action),
+                });
+            }
+        }
+    }
+
+    pub fn set(&mut self, action: Action, bindings: Vec<Binding>) {
+        match self.0.iter_mut().find(|entry| entry.action == action) {
+            Some(entry) => entry.bindings = bindings,
+            None => self.0.push(ActionBindings { action, bindings }),
+        }
+    }
+}
+
+/// Bindings a navigation preset gives a camera drag, none for the other actions.
+fn preset_bindings(action: Action, preset: NavigationPreset) -> Vec<Binding> {
+    use InputTrigger::Mouse;
+    use MouseButton::{Middle, Right};
+
+    // Orbit, pan and zoom drags of the preset.
+    let (orbit, pan, zoom) = match preset {
+        NavigationPreset::Andreo => (
+            Binding::new(Mouse(Right), Modifiers::NONE),
+            Binding::new(Mouse(Middle), Modifiers::NONE),
+            None,
+        ),
+        NavigationPreset::SketchUp => (
+            Binding::new(Mouse(Middle), Modifiers::NONE),
+            Binding::new(Mouse(Middle), Modifiers::SHIFT),
+            None,
+        ),
+        NavigationPreset::Blender => (
+            Binding::new(Mouse(Middle), Modifiers::NONE),
+            Binding::new(Mouse(Middle), Modifiers::SHIFT),
+            Some(Binding::new(Mouse(Middle), Modifiers::CTRL)),
+        ),
+        NavigationPreset::AutoCad => (
+            Binding::new(Mouse(Middle), Modifiers::SHIFT),
+            Binding::new(Mouse(Middle), Modifiers::NONE),
+            None,
+        ),
+        NavigationPreset::Laptop => (
+            Binding::new(Mouse(Right), Modifiers::NONE),
+            Binding::new(Mouse(Right), Modifiers::SHIFT),
+            Some(Binding::new(Mouse(Right), Modifiers::CTRL)),
+        ),
+    };
+
+    match action {
+        Action::CameraOrbit => vec![orbit],
+        Action::CameraPan => vec![pan],
+        Action::CameraZoom => zoom.into_iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Keys the quick bookmarks 1 to 9 are on.
+fn digit_key(index: u8) -> Option<KeyCode> {
+    [
+        KeyCode::Digit1,
+        KeyCode::Digit2,
+        KeyCode::Digit3,
+        KeyCode::Digit4,
+        KeyCode::Digit5,
+        KeyCode::Digit6,
+        KeyCode::Digit7,
+        KeyCode::Digit8,
+        KeyCode::Digit9,
+    ]
+    .get(usize::from(index).checked_sub(1)?)
+    .copied()
+}
+
+fn default_bindings(action: Action) -> Vec<Binding> {
+    use Action::*;
+    use KeyCode::*;
+
+    let ctrl = |key| Binding::new(InputTrigger::Key(key), Modifiers::CTRL);
+
+    match action {
+        // The navigation preset binds those, see `preset_bindings`.
+        CameraOrbit | CameraPan | CameraZoom => Vec::new(),
+        ZoomIn => vec![Binding::new(
+            InputTrigger::Wheel(WheelDirection::Up),
+            Modifiers::NONE,
+        )],
+        ZoomOut => vec![Binding::new(
+            InputTrigger::Wheel(WheelDirection::Down),
+            Modifiers::NONE,
+        )],
+        OpenContextMenu => vec![Binding::new(
+            InputTrigger::Click(MouseButton::Right),
+            Modifiers::NONE,
+        )],
+        TogglePlanView => vec![Binding::key(KeyP)],
+        ToggleWalkthrough => vec![Binding::key(KeyT)],
+        WalkForward => vec![Binding::key(KeyW), Binding::key(ArrowUp)],
+        WalkBackward => vec![Binding::key(KeyS), Binding::key(ArrowDown)],
+        WalkLeft => vec![Binding::key(KeyA), Binding::key(ArrowLeft)],
+        WalkRight => vec![Binding::key(KeyD), Binding::key(ArrowRight)],
+        Run => vec![Binding::key(ShiftLeft), Binding::key(ShiftRight)],
+        FrameAll => vec![Binding::key(Home)],
+        FrameSelected => vec![Binding::key(KeyF)],
+        // The navigation presets leave the left button free, the path tells
+        // the action from `KeyCode::Select`.
+        Action::Select => vec![Binding::new(
+            InputTrigger::Click(MouseButton::Left),
+            Modifiers::NONE,
+        )],
+        // Numpad views like in the CAD tools.
+        ViewTop => vec![Binding::key(Numpad7)],
+        ViewBottom => vec![ctrl(Numpad7)],
+        ViewFront => vec![Binding::key(Numpad1)],
+        ViewBack => vec![ctrl(Numpad1)],
+        ViewRight => vec![Binding::key(Numpad3)],
+        ViewLeft => vec![ctrl(Numpad3)],
+        ViewIsometric => vec![Binding::key(Numpad0)],
+        TurnLeft => vec![ctrl(ArrowLeft)],
+        TurnRight => vec![ctrl(ArrowRight)],
+        TurnUp => vec![ctrl(ArrowUp)],
+        TurnDown => vec![ctrl(ArrowDown)],
+        RecallBookmark(index) => digit_key(index).map(Binding::key).into_iter().collect(),
+        SaveBookmark(index) => digit_key(index).map(ctrl).into_iter().collect(),
+    }
+}
+
+/// Actions the bound inputs set off this frame. Inputs egui has captured
+/// don't count, see [`InputFocus`].
+#[derive(Resource, Default, Debug)]
+pub struct ActionState {
+    pressed: HashSet<Action>,
+    just_pressed: HashSet<Action>,
+    just_released: HashSet<Action>,
+    values: HashMap<Action, f32>,
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    pub fn just_released(&self, action: Action) -> bool {
+        self.just_released.contains(&action)
+    }
+
+    /// Wheel steps of wheel bindings, 1 for other held actions, 0 otherwise.
+    pub fn value(&self, action: Action) -> f32 {
+        self.values.get(&action).copied().unwrap_or_default()
+    }
+}
+
+pub struct ActionPlugin;
+
+impl Plugin for ActionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActionState>().add_systems(
+            PreUpdate,
+            update_action_state
+                .after(update_input_focus)
+                .after(ButtonGestureSystem),
+        );
+    }
+}
+
+/// Pixels a smooth scrolling wheel scrolls for a wheel step.
+const PIXELS_PER_LINE: f32 = 50.;
+
+fn update_action_state(
+    preferences: Res<Preferences>,
+    focus: Res<InputFocus>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ExtendedButtonInput<MouseButton>>,
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    mut state: ResMut<ActionState>,
+) {
+    // Touchpads scroll in pixels, those pan the camera instead of turning the wheel
+    // unless the preferences take them for a smooth scrolling wheel.
+    let pixel_scroll_pans = preferences.camera_control.pixel_scroll_pans;
+    let mut wheel = 0.;
+    for event in mouse_wheel_events.read() {
+        match event.unit {
+            MouseScrollUnit::Line => wheel += event.y,
+            MouseScrollUnit::Pixel if !pixel_scroll_pans => wheel += event.y / PIXELS_PER_LINE,
+            MouseScrollUnit::Pixel => {}
+        }
+    }
+    let wheel_steps = |direction| match direction {
+        WheelDirection::Up => wheel.max(0.),
+        WheelDirection::Down => (-wheel).max(0.),
+    };
+
+    let modifiers = Modifiers::pressed(&keyboard_input);
+    let preset = preferences.camera_control.navigation_preset;
+    let bindings: Vec<(Action, Binding)> = Action::all()
+        .flat_map(|action| {
+            preferences
+                .input_bindings
+                .get(action, preset)
+                .into_iter()
+                .map(move |binding| (action, binding))
+        })
+        .collect();
+
+    // Value of a binding this frame and whether its trigger just went down.
+    let evaluate = |binding: &Binding| -> Option<(f32, bool)> {
+        let focused = if binding.trigger.is_pointer() {
+            focus.scene_has_pointer()
+        } else {
+            focus.scene_has_keyboard()
+        };
+        if !focused || !binding.modifiers.within(modifiers) {
+            return None;
+        }
+
+        match binding.trigger {
+            InputTrigger::Key(key) => keyboard_input
+                .pressed(key)
+                .then(|| (1., keyboard_input.just_pressed(key))),
+            InputTrigger::Mouse(button) => mouse_input
+                .dragging(button)
+                .then(|| (1., mouse_input.drag_started(button))),
+            InputTrigger::Click(button) => mouse_input.clicked(button).then_some((1., true)),
+            InputTrigger::DoubleClick(button) => {
+                mouse_input.double_clicked(button).then_some((1., true))
+            }
+            InputTrigger::LongPress(button) => {
+                mouse_input.long_pressed(button).then_some((1., true))
+            }
+            InputTrigger::Wheel(direction) => {
+                let steps = wheel_steps(direction);
+                (steps > 0.).then_some((steps, true))
+            }
+        }
+    };
+    // A binding gives way to one on the same trigger that needs more of the held
+    // modifiers, Ctrl with a digit saves the bookmark instead of recalling it.
+    let overridden = |binding: &Binding| {
+        bindings.iter().any(|(_, other)| {
+            other.trigger == binding.trigger
+                && other.modifiers != binding.modifiers
+                && binding.modifiers.within(other.modifiers)
+                && other.modifiers.within(modifiers)
+        })
+    };
+
+    let previous = std::mem::take(&mut state.pressed);
+    state.just_pressed.clear();
+    state.just_released.clear();
+    state.values.clear();
+
+    for (action, binding) in &bindings {
+        if overridden(binding) {
+            continue;
+        }
+        let Some((value, just_pressed)) = evaluate(binding) else {
+            continue;
+        };
+
+        state.pressed.insert(*action);
+        *state.values.entry(*action).or_default() += value;
+        if just_pressed && !previous.contains(action) {
+            state.just_pressed.insert(*action);
+        }
+    }
+
+    let released: Vec<Action> = previous.difference(&state.pressed).copied().collect();
+    state.just_released.extend(released);
+}
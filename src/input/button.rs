@@ -0,0 +1,425 @@
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use bevy::{input::InputSystem, prelude::*, utils::HashMap, window::PrimaryWindow};
+
+/// Cursor travel in logical pixels that turns a press into a drag.
+const DRAG_THRESHOLD: f32 = 4.0;
+/// Seconds a still press takes to become a long press.
+const LONG_PRESS_DURATION: f32 = 0.5;
+/// Most seconds between two clicks of a double-click.
+const DOUBLE_CLICK_DURATION: f32 = 0.3;
+/// Farthest in logical pixels the second click of a double-click may land from the first.
+const DOUBLE_CLICK_DISTANCE: f32 = 6.0;
+
+/// Button type [`ExtendedButtonInput`] can classify.
+pub trait GestureButton: Copy + Eq + Hash + Send + Sync + std::fmt::Debug + 'static {
+    /// Whether moving the cursor while the button is held drags,
+    /// keys only click, double-click and long press.
+    const DRAGS: bool;
+}
+
+impl GestureButton for MouseButton {
+    const DRAGS: bool = true;
+}
+
+impl GestureButton for KeyCode {
+    const DRAGS: bool = false;
+}
+
+/// What a button press turned out to be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ButtonGesture {
+    /// Released quickly without moving.
+    Click,
+    /// Second click shortly after and close to the first, instead of a click.
+    DoubleClick,
+    /// Held still long enough, no click follows on release.
+    LongPress,
+    /// The cursor moved past the drag threshold while the button was held.
+    DragStart,
+    /// Released after a drag.
+    DragEnd,
+}
+
+/// Sent when a button gesture is recognized, with the cursor positions
+/// where the press started and where the gesture ended, in logical pixels.
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct ButtonGestureEvent<T: GestureButton> {
+    pub button: T,
+    pub gesture: ButtonGesture,
+    pub start: Vec2,
+    pub end: Vec2,
+}
+
+pub type MouseGestureEvent = ButtonGestureEvent<MouseButton>;
+
+/// Button held down, not yet released.
+#[derive(Debug, Clone, Copy)]
+struct Press {
+    start: Vec2,
+    /// Seconds since startup when the button went down.
+    start_time: f32,
+    dragging: bool,
+    long_pressed: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ButtonState {
+    press: Option<Press>,
+    /// Time and position of the last click, for the double-click.
+    last_click: Option<(f32, Vec2)>,
+    /// Gestures recognized this frame.
+    gestures: Vec<ButtonGesture>,
+}
+
+impl ButtonState {
+    /// Whether nothing about the button has to be remembered anymore.
+    fn is_idle(&self, now: f32) -> bool {
+        self.press.is_none()
+            && self.gestures.is_empty()
+            && self
+                .last_click
+                .map_or(true, |(time, _)| now - time > DOUBLE_CLICK_DURATION)
+    }
+}
+
+/// Buttons classified into clicks, double-clicks, long presses and drags.
+/// A button is tracked from its first press on, others read as not pressed.
+#[derive(Resource)]
+pub struct ExtendedButtonInput<T: GestureButton> {
+    buttons: HashMap<T, ButtonState>,
+}
+
+impl<T: GestureButton> Default for ExtendedButtonInput<T> {
+    fn default() -> Self {
+        Self {
+            buttons: HashMap::new(),
+        }
+    }
+}
+
+impl<T: GestureButton> ExtendedButtonInput<T> {
+    fn press(&self, button: T) -> Option<Press> {
+        self.buttons.get(&button).and_then(|state| state.press)
+    }
+
+    /// Whether the gesture was recognized this frame.
+    pub fn gesture(&self, button: T, gesture: ButtonGesture) -> bool {
+        self.buttons
+            .get(&button)
+            .map_or(false, |state| state.gestures.contains(&gesture))
+    }
+
+    pub fn clicked(&self, button: T) -> bool {
+        self.gesture(button, ButtonGesture::Click)
+    }
+
+    pub fn double_clicked(&self, button: T) -> bool {
+        self.gesture(button, ButtonGesture::DoubleClick)
+    }
+
+    pub fn long_pressed(&self, button: T) -> bool {
+        self.gesture(button, ButtonGesture::LongPress)
+    }
+
+    pub fn drag_started(&self, button: T) -> bool {
+        self.gesture(button, ButtonGesture::DragStart)
+    }
+
+    pub fn drag_ended(&self, button: T) -> bool {
+        self.gesture(button, ButtonGesture::DragEnd)
+    }
+
+    /// Whether the button is held and the cursor moved past the drag threshold.
+    pub fn dragging(&self, button: T) -> bool {
+        self.press(button).map_or(false, |press| press.dragging)
+    }
+
+    /// Whether the button is held and it can't be a click anymore, it is dragged or long pressed.
+    pub fn held(&self, button: T) -> bool {
+        self.press(button)
+            .map_or(false, |press| press.dragging || press.long_pressed)
+    }
+}
+
+/// Systems recognizing the button gestures of this frame.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ButtonGestureSystem;
+
+pub(super) fn button_press_system<T: GestureButton>(
+    time: Res<Time>,
+    mut button_input: ResMut<ExtendedButtonInput<T>>,
+    input: Res<ButtonInput<T>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut last_cursor: Local<Vec2>,
+    mut gesture_events: EventWriter<ButtonGestureEvent<T>>,
+) {
+    let now = time.elapsed_seconds();
+    // Keep the last known position while the cursor is outside the window.
+    if let Some(cursor) = window_query
+        .get_single()
+        .ok()
+        .and_then(Window::cursor_position)
+    {
+        *last_cursor = cursor;
+    }
+    let cursor = *last_cursor;
+
+    for button in input.get_just_pressed() {
+        button_input.buttons.entry(*button).or_default().press = Some(Press {
+            start: cursor,
+            start_time: now,
+            dragging: false,
+            long_pressed: false,
+        });
+    }
+
+    for (button, state) in button_input.buttons.iter_mut() {
+        state.gestures.clear();
+
+        let mut recognized = |state: &mut ButtonState, gesture, start| {
+            state.gestures.push(gesture);
+            gesture_events.send(ButtonGestureEvent {
+                button: *button,
+                gesture,
+                start,
+                end: cursor,
+            });
+        };
+
+        if let Some(mut press) = state.press {
+            if T::DRAGS && !press.dragging && press.start.distance(cursor) > DRAG_THRESHOLD {
+                press.dragging = true;
+                recognized(state, ButtonGesture::DragStart, press.start);
+            } else if !press.dragging
+                && !press.long_pressed
+                && now - press.start_time >= LONG_PRESS_DURATION
+            {
+                press.long_pressed = true;
+                recognized(state, ButtonGesture::LongPress, press.start);
+            }
+            state.press = Some(press);
+        }
+
+        // Also ends presses the app missed the release of, like a key let go outside the window.
+        if !input.pressed(*button) {
+            let Some(press) = state.press.take() else {
+                continue;
+            };
+
+            if press.dragging {
+                recognized(state, ButtonGesture::DragEnd, press.start);
+            } else if !press.long_pressed {
+                // Keys don't point anywhere, the cursor may have moved between their clicks.
+                let double_click = state.last_click.map_or(false, |(time, position)| {
+                    now - time <= DOUBLE_CLICK_DURATION
+                        && (!T::DRAGS || position.distance(cursor) <= DOUBLE_CLICK_DISTANCE)
+                });
+
+                if double_click {
+                    // A third click starts over instead of making another double-click.
+                    state.last_click = None;
+                    recognized(state, ButtonGesture::DoubleClick, press.start);
+                } else {
+                    state.last_click = Some((now, cursor));
+                    recognized(state, ButtonGesture::Click, press.start);
+                }
+            }
+        }
+    }
+
+    button_input.buttons.retain(|_, state| !state.is_idle(now));
+}
+
+/// Keeps an [`ExtendedButtonInput`] for the buttons of type `T`.
+pub struct ExtendedButtonInputPlugin<T>(PhantomData<T>);
+
+impl<T> Default for ExtendedButtonInputPlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: GestureButton> Plugin for ExtendedButtonInputPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ExtendedButtonInput<T>>()
+            .add_event::<ButtonGestureEvent<T>>()
+            .add_systems(
+                PreUpdate,
+                button_press_system::<T>
+                    .in_set(ButtonGestureSystem)
+                    .after(InputSystem),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::input::mouse::MouseButtonInput;
+    use bevy::input::InputPlugin;
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+
+    const FRAME: Duration = Duration::from_millis(50);
+    const START: Vec2 = Vec2::new(100., 100.);
+
+    /// Headless app classifying the mouse buttons, with the cursor at [`START`].
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            InputPlugin,
+            ExtendedButtonInputPlugin::<MouseButton>::default(),
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME));
+        app.world_mut().spawn((Window::default(), PrimaryWindow));
+        move_cursor(&mut app, START);
+        app.update();
+        app
+    }
+
+    fn move_cursor(app: &mut App, position: Vec2) {
+        app.world_mut()
+            .query_filtered::<&mut Window, With<PrimaryWindow>>()
+            .single_mut(app.world_mut())
+            .set_cursor_position(Some(position));
+    }
+
+    fn send_button(app: &mut App, state: bevy::input::ButtonState) {
+        let window = app
+            .world_mut()
+            .query_filtered::<Entity, With<PrimaryWindow>>()
+            .single(app.world());
+        app.world_mut().send_event(MouseButtonInput {
+            button: MouseButton::Left,
+            state,
+            window,
+        });
+    }
+
+    /// Runs `frames` frames, returns the gestures recognized in them.
+    fn run(app: &mut App, frames: u32) -> Vec<ButtonGesture> {
+        let mut gestures = Vec::new();
+        for _ in 0..frames {
+            app.update();
+            gestures.extend(
+                app.world_mut()
+                    .resource_mut::<Events<MouseGestureEvent>>()
+                    .drain()
+                    .map(|event| event.gesture),
+            );
+        }
+        gestures
+    }
+
+    fn press(app: &mut App) -> Vec<ButtonGesture> {
+        send_button(app, bevy::input::ButtonState::Pressed);
+        run(app, 1)
+    }
+
+    fn release(app: &mut App) -> Vec<ButtonGesture> {
+        send_button(app, bevy::input::ButtonState::Released);
+        run(app, 1)
+    }
+
+    fn click(app: &mut App) -> Vec<ButtonGesture> {
+        let mut gestures = press(app);
+        gestures.extend(release(app));
+        gestures
+    }
+
+    #[test]
+    fn small_moves_stay_a_click() {
+        let mut app = app();
+
+        assert_eq!(press(&mut app), []);
+        move_cursor(&mut app, START + Vec2::X * DRAG_THRESHOLD);
+        assert_eq!(run(&mut app, 1), []);
+        assert_eq!(release(&mut app), [ButtonGesture::Click]);
+    }
+
+    #[test]
+    fn moving_past_the_threshold_drags() {
+        let mut app = app();
+
+        press(&mut app);
+        move_cursor(&mut app, START + Vec2::X * (DRAG_THRESHOLD + 1.));
+        assert_eq!(run(&mut app, 1), [ButtonGesture::DragStart]);
+        assert!(app
+            .world()
+            .resource::<ExtendedButtonInput<MouseButton>>()
+            .dragging(MouseButton::Left));
+        assert_eq!(release(&mut app), [ButtonGesture::DragEnd]);
+    }
+
+    #[test]
+    fn holding_still_long_presses() {
+        let mut app = app();
+
+        press(&mut app);
+        let frames = (LONG_PRESS_DURATION / FRAME.as_secs_f32()) as u32 + 1;
+        assert_eq!(run(&mut app, frames), [ButtonGesture::LongPress]);
+        // No click follows, however long the button stays down.
+        assert_eq!(run(&mut app, frames), []);
+        assert_eq!(release(&mut app), []);
+    }
+
+    #[test]
+    fn quick_clicks_double_click() {
+        let mut app = app();
+
+        assert_eq!(click(&mut app), [ButtonGesture::Click]);
+        assert_eq!(click(&mut app), [ButtonGesture::DoubleClick]);
+    }
+
+    #[test]
+    fn distant_or_slow_clicks_stay_single() {
+        let mut app = app();
+
+        click(&mut app);
+        move_cursor(&mut app, START + Vec2::Y * (DOUBLE_CLICK_DISTANCE + 1.));
+        assert_eq!(click(&mut app), [ButtonGesture::Click]);
+
+        let frames = (DOUBLE_CLICK_DURATION / FRAME.as_secs_f32()) as u32 + 1;
+        run(&mut app, frames);
+        assert_eq!(click(&mut app), [ButtonGesture::Click]);
+    }
+
+    #[test]
+    fn third_click_starts_over() {
+        let mut app = app();
+
+        let gestures: Vec<_> = (0..4).flat_map(|_| click(&mut app)).collect();
+        assert_eq!(
+            gestures,
+            [
+                ButtonGesture::Click,
+                ButtonGesture::DoubleClick,
+                ButtonGesture::Click,
+                ButtonGesture::DoubleClick,
+            ]
+        );
+    }
+
+    #[test]
+    fn idle_buttons_are_forgotten() {
+        let mut app = app();
+        let tracked = |app: &App| {
+            app.world()
+                .resource::<ExtendedButtonInput<MouseButton>>()
+                .buttons
+                .len()
+        };
+
+        click(&mut app);
+        // Kept for a double-click until it is too late for one.
+        assert_eq!(tracked(&app), 1);
+        let frames = (DOUBLE_CLICK_DURATION / FRAME.as_secs_f32()) as u32 + 1;
+        run(&mut app, frames);
+        assert_eq!(tracked(&app), 0);
+    }
+}
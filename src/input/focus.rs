@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, EguiSet};
+
+/// Who a pointer gesture belongs to, decided when its first button goes down.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+enum PointerOwner {
+    #[default]
+    None,
+    Ui,
+    Scene,
+}
+
+/// Arbitrates the input between the egui windows and the scene.
+///
+/// A drag belongs to whoever it started over until all mouse buttons are released,
+/// so a camera drag keeps going when the cursor passes over a panel and a slider
+/// drag never turns the camera. Keyboard input goes to the scene unless an egui
+/// widget has the focus.
+#[derive(Resource, Default, Debug)]
+pub struct InputFocus {
+    pointer: PointerOwner,
+    pointer_over_ui: bool,
+    keyboard_to_ui: bool,
+}
+
+impl InputFocus {
+    /// Whether mouse buttons, motion and wheel should reach the camera and the scene tools.
+    pub fn scene_has_pointer(&self) -> bool {
+        match self.pointer {
+            PointerOwner::Scene => true,
+            PointerOwner::Ui => false,
+            PointerOwner::None => !self.pointer_over_ui,
+        }
+    }
+
+    /// Whether key presses should reach the camera and the scene shortcuts.
+    pub fn scene_has_keyboard(&self) -> bool {
+        !self.keyboard_to_ui
+    }
+}
+
+pub(super) fn update_input_focus(
+    mut contexts: EguiContexts,
+    mut focus: ResMut<InputFocus>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+) {
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+
+    focus.pointer_over_ui = ctx.is_pointer_over_area() || ctx.wants_pointer_input();
+    focus.keyboard_to_ui = ctx.wants_keyboard_input();
+
+    // The owner still gets the release of its last button.
+    let gesture_over = mouse_input.get_pressed().next().is_none()
+        && mouse_input.get_just_released().next().is_none();
+    if gesture_over {
+        focus.pointer = PointerOwner::None;
+    }
+
+    if focus.pointer == PointerOwner::None && mouse_input.get_just_pressed().next().is_some() {
+        focus.pointer = if focus.pointer_over_ui {
+            PointerOwner::Ui
+        } else {
+            PointerOwner::Scene
+        };
+    }
+}
+
+pub struct InputFocusPlugin;
+
+impl Plugin for InputFocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputFocus>().add_systems(
+            PreUpdate,
+            update_input_focus.after(EguiSet::BeginFrame),
+        );
+    }
+}
@@ -1,11 +1,20 @@
 use bevy::prelude::*;
 
-use super::mouse::MousePlugin;
+use super::{
+  action::ActionPlugin, button::ExtendedButtonInputPlugin, focus::InputFocusPlugin,
+  recording::InputRecordingPlugin,
+};
 
 pub struct InputPlugins;
 
 impl Plugin for InputPlugins {
   fn build(&self, app: &mut App) {
-    app.add_plugins(MousePlugin);
+    app.add_plugins((
+      ExtendedButtonInputPlugin::<MouseButton>::default(),
+      ExtendedButtonInputPlugin::<KeyCode>::default(),
+      InputFocusPlugin,
+      ActionPlugin,
+      InputRecordingPlugin,
+    ));
   }
 }
@@ -1,8 +1,17 @@
 #![allow(clippy::module_inception)]
 
-mod mouse;
+mod action;
+mod button;
+mod focus;
 mod input;
+mod recording;
 
+pub use action::*;
+pub use focus::InputFocus;
 pub use input::*;
+pub use recording::*;
 
-pub use mouse::ExtendedButtonInput;
+pub use button::{
+    ButtonGesture, ButtonGestureEvent, ButtonGestureSystem, ExtendedButtonInput,
+    ExtendedButtonInputPlugin, GestureButton, MouseGestureEvent,
+};
@@ -0,0 +1,413 @@
+use std::{
+    collections::VecDeque,
+    fs::{File, OpenOptions},
+    io::{self, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use bevy::{
+    ecs::system::SystemParam,
+    input::{
+        keyboard::KeyboardInput,
+        mouse::{MouseButtonInput, MouseMotion, MouseWheel},
+        InputSystem,
+    },
+    prelude::*,
+    time::TimeUpdateStrategy,
+    window::PrimaryWindow,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::util::{flag_value, has_flag};
+
+/// Command line flag followed by the file to record the input into.
+const RECORD_FLAG: &str = "--record";
+/// Command line flag followed by a recorded file to replay instead of the devices.
+const REPLAY_FLAG: &str = "--replay";
+/// Command line flag to quit once the replay is over, for replaying headlessly.
+const EXIT_AFTER_REPLAY_FLAG: &str = "--exit-after-replay";
+/// Command line flag followed by the milliseconds each replayed frame advances
+/// the clock by, instead of the recorded frame times.
+const REPLAY_STEP_FLAG: &str = "--replay-step";
+/// How often the recorded frames are written out, so a crash keeps the input leading to it.
+const RECORDING_FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Input event the app consumed, as it came from the window.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum RecordedEvent {
+    Keyboard(KeyboardInput),
+    MouseButton(MouseButtonInput),
+    MouseMotion(MouseMotion),
+    MouseWheel(MouseWheel),
+    CursorMoved(CursorMoved),
+}
+
+/// Input of one frame.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct RecordedFrame {
+    /// Time since the previous frame.
+    pub delta: Duration,
+    /// Cursor position in the primary window, in logical pixels.
+    pub cursor_position: Option<Vec2>,
+    pub events: Vec<RecordedEvent>,
+}
+
+/// Input stream of a session, frame by frame, to attach to a bug report
+/// and to replay.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct InputRecording {
+    pub frames: Vec<RecordedFrame>,
+}
+
+impl InputRecording {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let file = BufReader::new(File::open(path)?);
+        serde_yaml::from_reader(file).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let file = BufWriter::new(File::create(path)?);
+        serde_yaml::to_writer(file, self).map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+    }
+}
+
+/// Records the input while it exists into the file at `path`, appending the
+/// frames every few seconds and when the app exits.
+#[derive(Resource, Debug)]
+pub struct InputRecorder {
+    pub path: PathBuf,
+    /// Frames not written out yet.
+    pending: Vec<RecordedFrame>,
+    /// Whether the file was started, later frames are appended to it.
+    started: bool,
+    /// Time of the last write, from the real clock.
+    last_flush: Duration,
+}
+
+impl InputRecorder {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            pending: Vec::new(),
+            started: false,
+            last_flush: Duration::ZERO,
+        }
+    }
+
+    /// Appends the frames recorded since the last flush to the file, which
+    /// stays a valid recording in between.
+    pub fn flush(&mut self) -> io::Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let mut file = if self.started {
+            BufWriter::new(OpenOptions::new().append(true).open(&self.path)?)
+        } else {
+            let mut file = BufWriter::new(File::create(&self.path)?);
+            file.write_all(b"frames:\n")?;
+            file
+        };
+        // The frames go on the top level sequence under `frames`.
+        serde_yaml::to_writer(&mut file, &self.pending)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        file.flush()?;
+
+        self.started = true;
+        self.pending.clear();
+        Ok(())
+    }
+}
+
+/// Feeds a recording into the input systems in place of the devices, one frame
+/// per update with the recorded frame times, until it runs out.
+///
+/// The clock advances by the recorded frame times by default. The replay stays
+/// deterministic, and the inertia and transitions play out as they did in the
+/// session, whatever the frame rate it was recorded at.
+#[derive(Resource, Debug)]
+pub struct InputReplay {
+    frames: VecDeque<RecordedFrame>,
+    /// Exit the app after the last frame, see `--exit-after-replay`.
+    pub exit_when_done: bool,
+    /// Time step of every frame instead of the recorded ones, see `--replay-step`.
+    /// Headless runs of different recordings then step the same way.
+    pub fixed_step: Option<Duration>,
+}
+
+impl InputReplay {
+    pub fn new(recording: InputRecording) -> Self {
+        Self {
+            frames: recording.frames.into(),
+            exit_when_done: false,
+            fixed_step: None,
+        }
+    }
+
+    /// Starts the replay with the next update.
+    pub fn start(self, world: &mut World) {
+        world.insert_resource(self.time_strategy());
+        world.insert_resource(self);
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Time step of the next update, the recorded one or the real time once done.
+    fn time_strategy(&self) -> TimeUpdateStrategy {
+        self.frames
+            .front()
+            .map_or(TimeUpdateStrategy::Automatic, |frame| {
+                TimeUpdateStrategy::ManualDuration(self.fixed_step.unwrap_or(frame.delta))
+            })
+    }
+}
+
+pub struct InputRecordingPlugin;
+
+impl Plugin for InputRecordingPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(path) = flag_value(REPLAY_FLAG) {
+            match InputRecording::load(Path::new(&path)) {
+                Ok(recording) => InputReplay {
+                    exit_when_done: has_flag(EXIT_AFTER_REPLAY_FLAG),
+                    fixed_step: replay_step(),
+                    ..InputReplay::new(recording)
+                }
+                .start(app.world_mut()),
+                Err(err) => {
+                    log::error!("Failed to load input recording ({path:?}) \n error: {err:#?}")
+                }
+            }
+        }
+        if let Some(path) = flag_value(RECORD_FLAG) {
+            app.insert_resource(InputRecorder::new(path));
+        }
+
+        app.add_systems(
+            PreUpdate,
+            (
+                replay_input.run_if(resource_exists::<InputReplay>),
+                record_input.run_if(resource_exists::<InputRecorder>),
+            )
+                .chain()
+                .before(InputSystem),
+        )
+        .add_systems(
+            Last,
+            save_input_recording.run_if(resource_exists::<InputRecorder>),
+        );
+    }
+}
+
+/// Time step given with `--replay-step`, in milliseconds.
+fn replay_step() -> Option<Duration> {
+    let value = flag_value(REPLAY_STEP_FLAG)?;
+    match value.parse::<f64>() {
+        Ok(millis) if millis > 0. => Some(Duration::from_secs_f64(millis / 1000.)),
+        _ => {
+            log::error!("Invalid replay step {value:?}, replaying with the recorded frame times");
+            None
+        }
+    }
+}
+
+/// Readers of the device events a recording holds.
+#[derive(SystemParam)]
+struct DeviceEventReaders<'w, 's> {
+    keyboard: EventReader<'w, 's, KeyboardInput>,
+    mouse_button: EventReader<'w, 's, MouseButtonInput>,
+    mouse_motion: EventReader<'w, 's, MouseMotion>,
+    mouse_wheel: EventReader<'w, 's, MouseWheel>,
+    cursor_moved: EventReader<'w, 's, CursorMoved>,
+}
+
+/// Queues of the device events a replay refills.
+#[derive(SystemParam)]
+struct DeviceEvents<'w> {
+    keyboard: ResMut<'w, Events<KeyboardInput>>,
+    mouse_button: ResMut<'w, Events<MouseButtonInput>>,
+    mouse_motion: ResMut<'w, Events<MouseMotion>>,
+    mouse_wheel: ResMut<'w, Events<MouseWheel>>,
+    cursor_moved: ResMut<'w, Events<CursorMoved>>,
+}
+
+fn record_input(
+    time: Res<Time<Real>>,
+    mut recorder: ResMut<InputRecorder>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    device_events: DeviceEventReaders,
+) {
+    let DeviceEventReaders {
+        keyboard: mut keyboard_events,
+        mouse_button: mut mouse_button_events,
+        mouse_motion: mut mouse_motion_events,
+        mouse_wheel: mut mouse_wheel_events,
+        cursor_moved: mut cursor_moved_events,
+    } = device_events;
+    let mut events = Vec::new();
+    events.extend(keyboard_events.read().cloned().map(RecordedEvent::Keyboard));
+    events.extend(
+        mouse_button_events
+            .read()
+            .cloned()
+            .map(RecordedEvent::MouseButton),
+    );
+    events.extend(
+        mouse_motion_events
+            .read()
+            .cloned()
+            .map(RecordedEvent::MouseMotion),
+    );
+    events.extend(
+        mouse_wheel_events
+            .read()
+            .cloned()
+            .map(RecordedEvent::MouseWheel),
+    );
+    events.extend(
+        cursor_moved_events
+            .read()
+            .cloned()
+            .map(RecordedEvent::CursorMoved),
+    );
+
+    recorder.pending.push(RecordedFrame {
+        delta: time.delta(),
+        cursor_position: window_query
+            .get_single()
+            .ok()
+            .and_then(Window::cursor_position),
+        events,
+    });
+}
+
+fn save_input_recording(
+    time: Res<Time<Real>>,
+    mut recorder: ResMut<InputRecorder>,
+    mut exit_events: EventReader<AppExit>,
+) {
+    let exiting = exit_events.read().last().is_some();
+    if !exiting && time.elapsed() - recorder.last_flush < RECORDING_FLUSH_INTERVAL {
+        return;
+    }
+    recorder.last_flush = time.elapsed();
+
+    match recorder.flush() {
+        Ok(()) if exiting => log::info!("Saved input recording to {:?}", recorder.path),
+        Ok(()) => {}
+        Err(err) => log::error!(
+            "Failed to save input recording ({:?}) \n error: {:#?}",
+            recorder.path,
+            err
+        ),
+    }
+}
+
+/// Replaces this frame's device input with the next recorded frame.
+fn replay_input(
+    mut commands: Commands,
+    mut replay: ResMut<InputReplay>,
+    mut window_query: Query<(Entity, &mut Window), With<PrimaryWindow>>,
+    device_events: DeviceEvents,
+    mut exit_events: EventWriter<AppExit>,
+) {
+    let DeviceEvents {
+        keyboard: mut keyboard_events,
+        mouse_button: mut mouse_button_events,
+        mouse_motion: mut mouse_motion_events,
+        mouse_wheel: mut mouse_wheel_events,
+        cursor_moved: mut cursor_moved_events,
+    } = device_events;
+    keyboard_events.clear();
+    mouse_button_events.clear();
+    mouse_motion_events.clear();
+    mouse_wheel_events.clear();
+    cursor_moved_events.clear();
+
+    let Some(frame) = replay.frames.pop_front() else {
+        commands.remove_resource::<InputReplay>();
+        commands.insert_resource(TimeUpdateStrategy::Automatic);
+        log::info!("Input replay finished");
+        if replay.exit_when_done {
+            exit_events.send(AppExit::Success);
+        }
+        return;
+    };
+    commands.insert_resource(replay.time_strategy());
+
+    // The recorded events point at the window of the recording session.
+    let mut primary_window = window_query.get_single_mut().ok();
+    if let Some((_, window)) = primary_window.as_mut() {
+        if window.cursor_position() != frame.cursor_position {
+            window.set_cursor_position(frame.cursor_position);
+        }
+    }
+    let window_entity = |recorded| {
+        primary_window
+            .as_ref()
+            .map_or(recorded, |(entity, _)| *entity)
+    };
+
+    for event in frame.events {
+        match event {
+            RecordedEvent::Keyboard(mut event) => {
+                event.window = window_entity(event.window);
+                keyboard_events.send(event);
+            }
+            RecordedEvent::MouseButton(mut event) => {
+                event.window = window_entity(event.window);
+                mouse_button_events.send(event);
+            }
+            RecordedEvent::MouseMotion(event) => {
+                mouse_motion_events.send(event);
+            }
+            RecordedEvent::MouseWheel(mut event) => {
+                event.window = window_entity(event.window);
+                mouse_wheel_events.send(event);
+            }
+            RecordedEvent::CursorMoved(mut event) => {
+                event.window = window_entity(event.window);
+                cursor_moved_events.send(event);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use super::*;
+
+    #[test]
+    fn flushed_frames_load_back() {
+        let path = env::temp_dir().join(format!("andreo-recording-{}.yaml", std::process::id()));
+        let frame = |millis| RecordedFrame {
+            delta: Duration::from_millis(millis),
+            cursor_position: Some(Vec2::new(3., 12.)),
+            events: vec![RecordedEvent::MouseMotion(MouseMotion {
+                delta: Vec2::new(millis as f32, 0.),
+            })],
+        };
+
+        let mut recorder = InputRecorder::new(&path);
+        recorder.pending.extend([frame(16), frame(17)]);
+        recorder.flush().unwrap();
+        // Nothing new, the file stays as it is.
+        recorder.flush().unwrap();
+        recorder.pending.push(frame(18));
+        recorder.flush().unwrap();
+
+        let recording = InputRecording::load(&path);
+        fs::remove_file(&path).unwrap();
+        let deltas: Vec<_> = recording
+            .unwrap()
+            .frames
+            .iter()
+            .map(|frame| frame.delta.as_millis())
+            .collect();
+        assert_eq!(deltas, [16, 17, 18]);
+    }
+}
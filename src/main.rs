@@ -1,4 +1,4 @@
-use andreo::{core::LocationPlugin, input::InputPlugins, preference::PreferencesPlugin, ui::UiPlugins, CM};
+use andreo::{core::{Floor, LocationPlugin}, input::InputPlugins, preference::PreferencesPlugin, ui::UiPlugins, CM};
 use bevy::prelude::*;
 use bevy_editor_pls::prelude::*;
 use bevy_egui::EguiPlugin;
@@ -29,11 +29,14 @@ fn init_plan(
   mut meshes: ResMut<Assets<Mesh>>,
   mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
-  commands.spawn(PbrBundle {
-    mesh: meshes.add(Plane3d::default().mesh().size(20., 20.)),
-    material: materials.add(Color::WHITE),
-    ..default()
-  });
+  commands.spawn((
+    PbrBundle {
+      mesh: meshes.add(Plane3d::default().mesh().size(20., 20.)),
+      material: materials.add(Color::WHITE),
+      ..default()
+    },
+    Floor,
+  ));
 
   commands.spawn(PbrBundle {
     mesh: meshes.add(Cuboid::new(mc(27.), mc(270.), mc(675.))),
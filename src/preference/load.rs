@@ -0,0 +1,330 @@
+use std::{fs, io, path::Path};
+
+use serde_yaml::{Mapping, Value};
+
+use super::{
+    migration::{migrate_preferences, PREFERENCES_VERSION},
+    CameraControlPreferences, Preferences,
+};
+
+/// Setting `key` of `base` to `value`, or None when `base` isn't a mapping.
+fn with_value(base: &Value, key: &Value, value: Value) -> Option<Value> {
+    let mut base = base.as_mapping()?.clone();
+    base.insert(key.clone(), value);
+    Some(Value::Mapping(base))
+}
+
+/// Merges `user` into the valid preferences `base` key by key, keeping a key only if the
+/// preferences still deserialize with it. `rebuild` puts a candidate for `base` back into
+/// the whole preferences document.
+fn merge(
+    base: &mut Value,
+    user: &Mapping,
+    prefix: &str,
+    rebuild: &dyn Fn(Value) -> Value,
+    problems: &mut Vec<String>,
+) {
+    for (key, value) in user {
+        let name = format!("{prefix}{}", key.as_str().unwrap_or("?"));
+        let Some(candidate) = with_value(base, key, value.clone()) else {
+            continue;
+        };
+        if serde_yaml::from_value::<Preferences>(rebuild(candidate.clone())).is_ok() {
+            *base = candidate;
+            continue;
+        }
+
+        // A section with one bad setting keeps the rest of its settings.
+        let section = base.get(key).cloned();
+        match (section, value.as_mapping()) {
+            (Some(mut section @ Value::Mapping(_)), Some(user_section)) => {
+                let rebuild_section = |section: Value| {
+                    rebuild(with_value(base, key, section).unwrap_or_else(|| base.clone()))
+                };
+                merge(
+                    &mut section,
+                    user_section,
+                    &format!("{name}."),
+                    &rebuild_section,
+                    problems,
+                );
+                if let Some(merged) = with_value(base, key, section) {
+                    *base = merged;
+                }
+            }
+            _ => problems.push(format!("Ignored the invalid value of `{name}`")),
+        }
+    }
+}
+
+/// Preferences read from a file, with what had to change on the way.
+#[derive(Debug)]
+pub struct ParsedPreferences {
+    pub preferences: Preferences,
+    /// Settings that were dropped or reset to their defaults.
+    pub problems: Vec<String>,
+    /// Version of the file when it was older than [`PREFERENCES_VERSION`](super::PREFERENCES_VERSION).
+    pub migrated_from: Option<u32>,
+    /// Version of the file when it is newer than this app knows. Writing it would lose
+    /// the settings this app doesn't know, so it is only read.
+    pub newer_version: Option<u32>,
+}
+
+impl ParsedPreferences {
+    fn reset(problem: String) -> Self {
+        Self {
+            preferences: Preferences::default(),
+            problems: vec![problem],
+            migrated_from: None,
+            newer_version: None,
+        }
+    }
+}
+
+/// Parses the preferences, upgrading older files. Settings that are missing, don't parse
+/// or are out of range get their defaults.
+pub fn parse_preferences(text: &str) -> ParsedPreferences {
+    let mut user = match serde_yaml::from_str::<Value>(text) {
+        Ok(Value::Mapping(user)) => user,
+        Ok(Value::Null) => Mapping::new(),
+        Ok(_) => {
+            return ParsedPreferences::reset(
+                "The preferences file holds no settings, all settings were reset".to_owned(),
+            )
+        }
+        Err(err) => {
+            return ParsedPreferences::reset(format!(
+                "The preferences file is not valid YAML, all settings were reset: {err}"
+            ))
+        }
+    };
+
+    let mut problems = Vec::new();
+    let version = match migrate_preferences(&mut user, &mut problems) {
+        Ok(version) => Some(version),
+        // Read what still fits the current settings.
+        Err(problem) => {
+            problems.push(problem);
+            None
+        }
+    };
+    let migrated_from = version.filter(|&version| version < PREFERENCES_VERSION);
+    let newer_version = version.filter(|&version| version > PREFERENCES_VERSION);
+    if let Some(version) = newer_version {
+        problems.push(format!(
+            "The preferences are of version {version}, newer than {PREFERENCES_VERSION} this app knows"
+        ));
+    }
+
+    let mut preferences: Preferences = serde_yaml::from_value(Value::Mapping(user.clone()))
+        .unwrap_or_else(|_| {
+            let mut merged = serde_yaml::to_value(Preferences::default()).unwrap_or_default();
+            merge(&mut merged, &user, "", &|value| value, &mut problems);
+            serde_yaml::from_value(merged).unwrap_or_default()
+        });
+    // Actions the file doesn't bind get their defaults.
+    preferences.input_bindings.fill_missing();
+    problems.extend(preferences.validate());
+
+    ParsedPreferences {
+        preferences,
+        problems,
+        migrated_from,
+        newer_version,
+    }
+}
+
+/// Reads the preferences file, repairing what it can. Only an unreadable file is an error.
+pub(super) fn read_preferences_file(path: &Path) -> io::Result<ParsedPreferences> {
+    let text = fs::read_to_string(path)?;
+    Ok(parse_preferences(&text))
+}
+
+fn non_negative(value: f32) -> bool {
+    value >= 0.
+}
+
+fn positive(value: f32) -> bool {
+    value > 0.
+}
+
+impl CameraControlPreferences {
+    /// Resets the settings that are out of their range to the defaults, returns what was reset.
+    pub fn validate(&mut self) -> Vec<String> {
+        let default = Self::default();
+        let mut problems = Vec::new();
+        let mut check = |name: &str, value: &mut f32, default: f32, valid: fn(f32) -> bool| {
+            if !value.is_finite() || !valid(*value) {
+                problems.push(format!(
+                    "`camera_control.{name}` of {value} is out of range, reset to {default}"
+                ));
+                *value = default;
+            }
+        };
+
+        check(
+            "rotation_sensitivity",
+            &mut self.rotation_sensitivity,
+            default.rotation_sensitivity,
+            non_negative,
+        );
+        check(
+            "pan_sensitivity",
+            &mut self.pan_sensitivity,
+            default.pan_sensitivity,
+            non_negative,
+        );
+        check(
+            "zoom_sensitivity",
+            &mut self.zoom_sensitivity,
+            default.zoom_sensitivity,
+            non_negative,
+        );
+        check(
+            "min_distance",
+            &mut self.min_distance,
+            default.min_distance,
+            positive,
+        );
+        check(
+            "max_distance",
+            &mut self.max_distance,
+            default.max_distance,
+            positive,
+        );
+        check(
+            "inertia_damping",
+            &mut self.inertia_damping,
+            default.inertia_damping,
+            non_negative,
+        );
+        check(
+            "transition_duration",
+            &mut self.transition_duration,
+            default.transition_duration,
+            non_negative,
+        );
+        check(
+            "eye_height_cm",
+            &mut self.eye_height_cm,
+            default.eye_height_cm,
+            non_negative,
+        );
+        check(
+            "walk_speed_cm",
+            &mut self.walk_speed_cm,
+            default.walk_speed_cm,
+            non_negative,
+        );
+        check(
+            "turn_step_degrees",
+            &mut self.turn_step_degrees,
+            default.turn_step_degrees,
+            non_negative,
+        );
+        check(
+            "touch_pan_sensitivity",
+            &mut self.touch_pan_sensitivity,
+            default.touch_pan_sensitivity,
+            non_negative,
+        );
+        check(
+            "pinch_sensitivity",
+            &mut self.pinch_sensitivity,
+            default.pinch_sensitivity,
+            non_negative,
+        );
+        check(
+            "touch_rotation_sensitivity",
+            &mut self.touch_rotation_sensitivity,
+            default.touch_rotation_sensitivity,
+            non_negative,
+        );
+        check(
+            "gamepad_dead_zone",
+            &mut self.gamepad_dead_zone,
+            default.gamepad_dead_zone,
+            |value| (0. ..1.).contains(&value),
+        );
+        check(
+            "gamepad_look_sensitivity",
+            &mut self.gamepad_look_sensitivity,
+            default.gamepad_look_sensitivity,
+            non_negative,
+        );
+        check(
+            "gamepad_move_sensitivity",
+            &mut self.gamepad_move_sensitivity,
+            default.gamepad_move_sensitivity,
+            non_negative,
+        );
+        check(
+            "min_height_cm",
+            &mut self.min_height_cm,
+            default.min_height_cm,
+            non_negative,
+        );
+        check(
+            "min_pitch_degrees",
+            &mut self.min_pitch_degrees,
+            default.min_pitch_degrees,
+            |value| (-90. ..=90.).contains(&value),
+        );
+        check(
+            "max_pitch_degrees",
+            &mut self.max_pitch_degrees,
+            default.max_pitch_degrees,
+            |value| (-90. ..=90.).contains(&value),
+        );
+        check(
+            "bounds_margin_cm",
+            &mut self.bounds_margin_cm,
+            default.bounds_margin_cm,
+            non_negative,
+        );
+
+        if self.min_distance > self.max_distance {
+            problems.push(format!(
+                "`camera_control.min_distance` of {} is above `max_distance` of {}, both were reset",
+                self.min_distance, self.max_distance
+            ));
+            self.min_distance = default.min_distance;
+            self.max_distance = default.max_distance;
+        }
+        if self.min_pitch_degrees > self.max_pitch_degrees {
+            problems.push(format!(
+                "`camera_control.min_pitch_degrees` of {} is above `max_pitch_degrees` of {}, both were reset",
+                self.min_pitch_degrees, self.max_pitch_degrees
+            ));
+            self.min_pitch_degrees = default.min_pitch_degrees;
+            self.max_pitch_degrees = default.max_pitch_degrees;
+        }
+
+        problems
+    }
+}
+
+impl Preferences {
+    /// Resets the settings that are out of their range to the defaults, returns what was reset.
+    pub fn validate(&mut self) -> Vec<String> {
+        let mut problems = self.camera_control.validate();
+
+        let bookmarks = self.camera_bookmarks.len();
+        self.camera_bookmarks.retain(|bookmark| {
+            let values = [bookmark.yaw, bookmark.pitch, bookmark.distance];
+            values
+                .into_iter()
+                .chain(bookmark.point_of_view)
+                .all(f32::is_finite)
+                && bookmark.distance > 0.
+        });
+        let dropped = bookmarks - self.camera_bookmarks.len();
+        if dropped > 0 {
+            problems.push(format!(
+                "Dropped {dropped} camera bookmarks with invalid values"
+            ));
+        }
+
+        problems
+    }
+}
@@ -0,0 +1,70 @@
+use serde_yaml::{Mapping, Value};
+
+/// Version of the preferences file this build reads and writes.
+pub const PREFERENCES_VERSION: u32 = 1;
+
+const VERSION_KEY: &str = "version";
+
+/// Upgrade of the preferences document by one version, with the settings it
+/// couldn't carry over in the problems.
+type Migration = fn(&mut Mapping, &mut Vec<String>);
+
+/// Migration at index `n` upgrades version `n` to `n + 1`.
+const MIGRATIONS: [Migration; PREFERENCES_VERSION as usize] = [v0_to_v1];
+
+fn rename_key(mapping: &mut Mapping, from: &str, to: &str) {
+    if let Some(value) = mapping.remove(from) {
+        mapping.insert(to.into(), value);
+    }
+}
+
+fn section_mut<'a>(mapping: &'a mut Mapping, key: &str) -> Option<&'a mut Mapping> {
+    mapping.get_mut(key).and_then(Value::as_mapping_mut)
+}
+
+/// Zoom sensitivity version 0 defaulted to, it gives about the zoom of the current default.
+const V0_DEFAULT_ZOOM_SENSITIVITY: f64 = 0.5;
+
+/// Files before the version key: the camera section was misspelled `camera_controll`,
+/// the wheel zoomed by a fixed distance instead of a share of the distance and the
+/// inertia slowed down by a fixed step a frame. Neither translates to the current settings.
+fn v0_to_v1(preferences: &mut Mapping, problems: &mut Vec<String>) {
+    rename_key(preferences, "camera_controll", "camera_control");
+    if let Some(camera) = section_mut(preferences, "camera_control") {
+        camera.remove("inertia_decrement_speed");
+
+        let zoom_sensitivity = camera.get("zoom_sensitivity").and_then(Value::as_f64);
+        if zoom_sensitivity.is_some_and(|zoom| zoom != V0_DEFAULT_ZOOM_SENSITIVITY) {
+            camera.remove("zoom_sensitivity");
+            problems.push(
+                "The zoom sensitivity was reset, the wheel now zooms by a share of the distance"
+                    .to_owned(),
+            );
+        }
+    }
+}
+
+/// Upgrades the preferences document step by step to [`PREFERENCES_VERSION`] and returns
+/// the version it had, adding the settings it had to reset to `problems`. Files without
+/// a version are version 0, newer files are left as they are.
+pub fn migrate_preferences(
+    preferences: &mut Mapping,
+    problems: &mut Vec<String>,
+) -> Result<u32, String> {
+    let version = match preferences.get(VERSION_KEY) {
+        None => 0,
+        Some(value) => value
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| format!("`{VERSION_KEY}` of {value:?} is not a version number"))?,
+    };
+    if version > PREFERENCES_VERSION {
+        return Ok(version);
+    }
+
+    for migration in &MIGRATIONS[version as usize..] {
+        migration(preferences, problems);
+    }
+    preferences.insert(VERSION_KEY.into(), PREFERENCES_VERSION.into());
+    Ok(version)
+}
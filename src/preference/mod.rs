@@ -1,4 +1,10 @@
 #![allow(clippy::module_inception)]
 
+mod load;
+mod migration;
+mod path;
 mod preference;
+mod save;
+pub use load::{parse_preferences, ParsedPreferences};
+pub use migration::{migrate_preferences, PREFERENCES_VERSION};
 pub use preference::*;
@@ -0,0 +1,69 @@
+use std::{env, path::PathBuf};
+
+use crate::util::flag_value;
+
+const APP_NAME: &str = "andreo";
+const FILE_NAME: &str = "preferences";
+
+/// Command line flag with the preferences file to use, `--config path` or `--config=path`.
+const CONFIG_FLAG: &str = "--config";
+/// Environment variable with the preferences file to use, the flag wins over it.
+const CONFIG_ENV: &str = "ANDREO_CONFIG";
+
+/// Environment variable holding an absolute path.
+fn absolute_var(name: &str) -> Option<PathBuf> {
+    env::var_os(name)
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+}
+
+/// Per-user configuration directory of the platform, `$XDG_CONFIG_HOME` or `~/.config` on Linux.
+fn user_config_dir() -> Option<PathBuf> {
+    if cfg!(windows) {
+        return absolute_var("APPDATA");
+    }
+    let home = absolute_var("HOME");
+    if cfg!(target_os = "macos") {
+        return home.map(|home| home.join("Library/Application Support"));
+    }
+    absolute_var("XDG_CONFIG_HOME").or_else(|| home.map(|home| home.join(".config")))
+}
+
+/// Preferences files to try in a config directory, the first one is created when none exists.
+fn file_names() -> [String; 2] {
+    [format!("{FILE_NAME}.yaml"), format!("{FILE_NAME}.yml")]
+}
+
+/// Where the preferences are read from and saved to: the `--config` flag, else the
+/// `ANDREO_CONFIG` environment variable, else the user config directory.
+/// None when there is no place for them.
+pub(super) fn preferences_path() -> Option<PathBuf> {
+    if let Some(path) = flag_value(CONFIG_FLAG) {
+        return Some(path.into());
+    }
+    if let Some(path) = env::var_os(CONFIG_ENV).filter(|path| !path.is_empty()) {
+        return Some(path.into());
+    }
+
+    let dir = user_config_dir()?.join(APP_NAME);
+    let paths = file_names().map(|name| dir.join(name));
+    let existing = paths.iter().find(|path| path.exists());
+    Some(existing.unwrap_or(&paths[0]).clone())
+}
+
+/// Preferences shipped system-wide in `$XDG_CONFIG_DIRS`, like by a package,
+/// the user starts from on the first run.
+pub(super) fn system_preferences_path() -> Option<PathBuf> {
+    if cfg!(any(windows, target_os = "macos")) {
+        return None;
+    }
+    let dirs = env::var("XDG_CONFIG_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| "/etc/xdg".to_owned());
+
+    env::split_paths(&dirs)
+        .filter(|dir| dir.is_absolute())
+        .flat_map(|dir| file_names().map(|name| dir.join(APP_NAME).join(name)))
+        .find(|path| path.exists())
+}
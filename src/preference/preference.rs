@@ -1,59 +1,222 @@
 use std::{
-    env,
     fs::{File, OpenOptions},
-    io::Write,
-    path::PathBuf,
+    io,
+    path::{Path, PathBuf},
     sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 use bevy::{
-    app::{App, Last, Plugin, PostStartup},
+    app::{App, AppExit, Last, Plugin, PostStartup},
     ecs::{
         event::{Event, EventReader},
-        system::{Commands, Res,  Resource},
+        schedule::IntoSystemConfigs,
+        system::{Commands, Local, Res, ResMut, Resource, SystemParam},
     },
     prelude::{Deref, DerefMut},
+    time::{Real, Time},
 };
 use serde::{self, Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
 
-const FILE_NAME: &'static str = "preferences";
+use crate::input::InputBindings;
+
+use super::{
+    load::read_preferences_file,
+    migration::PREFERENCES_VERSION,
+    path::{preferences_path, system_preferences_path},
+    save::{write_preferences_file, FileStamp},
+};
+use strum_macros::{AsRefStr, EnumIter};
+
+/// Seconds after the last Apply before the preferences are written.
+const SAVE_DELAY: f32 = 1.0;
+/// Seconds between checks of the preferences file for edits made outside the app.
+const WATCH_INTERVAL: f32 = 0.5;
 
 #[allow(dead_code)]
 #[derive(Debug, Resource, Default, Clone, Deref, DerefMut)]
 struct AppliedPreferences(Preferences);
 
 #[derive(Deserialize, Serialize, Debug, Resource, Clone)]
-pub struct CameraControllPreferences {
+#[serde(default)]
+pub struct CameraControlPreferences {
     pub rotation_sensitivity: f32,
+    /// Orbit around the point under the cursor instead of the point of view.
+    pub orbit_around_cursor: bool,
     pub pan_sensitivity: f32,
     pub zoom_sensitivity: f32,
+    /// Zoom towards the point under the cursor instead of the point of view.
+    pub zoom_to_cursor: bool,
     pub min_distance: f32,
     pub max_distance: f32,
     pub inertia_on: bool,
-    pub inertia_decrement_speed: f32,
+    /// How quickly the camera stops gliding, per second.
+    pub inertia_damping: f32,
+    /// Seconds the camera takes to animate to a recalled view.
+    pub transition_duration: f32,
+    /// Height of the eye above the floor in the walkthrough, in centimeters.
+    pub eye_height_cm: f32,
+    /// Walking speed in the walkthrough, in centimeters per second.
+    pub walk_speed_cm: f32,
+    /// Angle the camera turns around the point of view with Ctrl and the arrow keys.
+    pub turn_step_degrees: f32,
+    /// Mouse buttons and modifier keys that orbit, pan and zoom.
+    pub navigation_preset: NavigationPreset,
+    /// Pan with the scrolls in pixels, the two-finger scroll of touchpads. Off they zoom
+    /// like wheel steps, for mice with a smooth scrolling wheel that scroll in pixels too.
+    pub pixel_scroll_pans: bool,
+    /// Scale of the two-finger scroll pan on touchpads and touchscreens.
+    pub touch_pan_sensitivity: f32,
+    /// Scale of the pinch zoom, 1 zooms as much as the fingers spread.
+    pub pinch_sensitivity: f32,
+    /// Scale of the two-finger rotation turning the camera around the vertical axis.
+    pub touch_rotation_sensitivity: f32,
+    /// Part of the stick and trigger travel that is ignored, from 0 to 1.
+    pub gamepad_dead_zone: f32,
+    /// Turn speed of a fully pushed right stick, in radians per second.
+    pub gamepad_look_sensitivity: f32,
+    /// Move and zoom speed of a fully pushed left stick or trigger, in orbit distances per second.
+    pub gamepad_move_sensitivity: f32,
+    /// Keep the camera above the floor, in the pitch range and around the scene.
+    pub limits_on: bool,
+    /// Lowest height of the orbit camera above the floor, in centimeters.
+    pub min_height_cm: f32,
+    /// Steepest downward orbit pitch.
+    pub min_pitch_degrees: f32,
+    /// Steepest upward orbit pitch.
+    pub max_pitch_degrees: f32,
+    /// How far the point of view may leave the scene bounds, in centimeters.
+    pub bounds_margin_cm: f32,
 }
 
-impl Default for CameraControllPreferences {
+impl Default for CameraControlPreferences {
     fn default() -> Self {
         Self { 
           rotation_sensitivity: 0.005,
+          orbit_around_cursor: false,
           pan_sensitivity: 0.01,
           zoom_sensitivity: 0.5,
+          zoom_to_cursor: false,
           min_distance: 1.0,
           max_distance: 100.0,
           inertia_on: false,
-          inertia_decrement_speed: 0.02,
+          inertia_damping: 8.0,
+          transition_duration: 0.5,
+          eye_height_cm: 160.0,
+          walk_speed_cm: 140.0,
+          turn_step_degrees: 15.0,
+          navigation_preset: NavigationPreset::default(),
+          pixel_scroll_pans: true,
+          touch_pan_sensitivity: 1.0,
+          pinch_sensitivity: 1.0,
+          touch_rotation_sensitivity: 1.0,
+          gamepad_dead_zone: 0.15,
+          gamepad_look_sensitivity: 2.0,
+          gamepad_move_sensitivity: 1.0,
+          limits_on: true,
+          min_height_cm: 30.0,
+          min_pitch_degrees: -89.9,
+          max_pitch_degrees: 89.9,
+          bounds_margin_cm: 100.0,
         }
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, Resource, Clone, Default)]
+/// Mouse navigation habits of other modeling tools, the camera drags follow the one
+/// picked instead of the [`InputBindings`].
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq, EnumIter, AsRefStr)]
+pub enum NavigationPreset {
+    /// Right button orbits, middle button pans.
+    #[default]
+    Andreo,
+    /// Middle button orbits, Shift with the middle button pans.
+    SketchUp,
+    /// Middle button orbits, Shift pans and Ctrl zooms with the middle button.
+    Blender,
+    /// Shift with the middle button orbits, middle button pans.
+    AutoCad,
+    /// For touchpads without a middle button: right button orbits,
+    /// Shift pans and Ctrl zooms with the right button.
+    Laptop,
+}
+
+/// How the window is split into editor viewports.
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq, EnumIter, AsRefStr)]
+pub enum ViewportLayout {
+    /// One perspective viewport.
+    #[default]
+    Single,
+    /// Top view next to the perspective view.
+    Two,
+    /// Top, perspective, front and side views.
+    Quad,
+}
+
+/// Named camera viewpoint the user can jump back to.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct CameraBookmark {
+    pub name: String,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub distance: f32,
+    pub point_of_view: [f32; 3],
+}
+
+#[derive(Deserialize, Serialize, Debug, Resource, Clone)]
+#[serde(default)]
 pub struct Preferences {
-    pub camera_controll: CameraControllPreferences,
+    /// Schema version of the file, older files are migrated when they are read.
+    pub version: u32,
+    pub camera_control: CameraControlPreferences,
+    pub camera_bookmarks: Vec<CameraBookmark>,
+    pub viewport_layout: ViewportLayout,
+    pub input_bindings: InputBindings,
 }
 
-#[derive(Debug, Resource, Deref)]
-struct PreferencesPath(Arc<PathBuf>);
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            version: PREFERENCES_VERSION,
+            camera_control: CameraControlPreferences::default(),
+            camera_bookmarks: Vec::new(),
+            viewport_layout: ViewportLayout::default(),
+            input_bindings: InputBindings::default(),
+        }
+    }
+}
+
+/// Preferences file the app saves to, with how it looked when the app last read or wrote it.
+#[derive(Debug, Resource)]
+struct PreferencesFile {
+    path: Arc<PathBuf>,
+    stamp: Option<FileStamp>,
+}
+
+impl PreferencesFile {
+    fn new(path: PathBuf) -> Self {
+        let stamp = FileStamp::of(&path);
+        Self {
+            path: path.into(),
+            stamp,
+        }
+    }
+}
+
+/// Real time in seconds at which the applied preferences are due to be saved.
+#[derive(Debug, Resource, Default)]
+struct PendingSave(Option<f32>);
+
+/// Problems with loading or saving the preferences, shown to the user until dismissed.
+#[derive(Debug, Resource, Default)]
+pub struct PreferencesProblems(pub Vec<String>);
+
+impl PreferencesProblems {
+    pub fn report(&mut self, problem: String) {
+        log::error!("{problem}");
+        self.0.push(problem);
+    }
+}
 
 #[derive(Debug, Event)]
 pub struct ApplyPreferencesEvent;
@@ -61,18 +224,173 @@ pub struct ApplyPreferencesEvent;
 #[derive(Debug, Event)]
 pub struct ExemptPreferencesEvent;
 
+/// Applies and saves only the camera bookmarks, other edits pending in the preferences
+/// window stay pending.
+#[derive(Debug, Event)]
+pub struct ApplyBookmarksEvent;
+
 pub struct PreferencesPlugin;
 
 impl Plugin for PreferencesPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<AppliedPreferences>()
+            .init_resource::<PreferencesProblems>()
+            .init_resource::<PendingSave>()
             .add_event::<ApplyPreferencesEvent>()
             .add_event::<ExemptPreferencesEvent>()
+            .add_event::<ApplyBookmarksEvent>()
             .add_systems(PostStartup, setup)
-            .add_systems(Last, (apply_preferences, exempt_preferences));
+            .add_systems(
+                Last,
+                (
+                    reload_preferences,
+                    apply_preferences,
+                    apply_bookmarks,
+                    exempt_preferences,
+                    save_preferences,
+                )
+                    .chain(),
+            );
     }
 }
 
+/// Progress of the watch over the preferences file.
+#[derive(Debug, Default)]
+struct FileWatch {
+    /// Real time in seconds of the last check.
+    last_check: f32,
+    /// Stamp of an edit seen at the last check, waiting to stay the same.
+    settling_stamp: Option<FileStamp>,
+}
+
+/// Applied preferences together with the preferences window edits on top of them.
+#[derive(SystemParam)]
+struct EditedPreferences<'w> {
+    edited: ResMut<'w, Preferences>,
+    applied: ResMut<'w, AppliedPreferences>,
+}
+
+/// Rebases the edits made to `applied` onto `reloaded`: settings edited since keep
+/// the edit, the others take the reloaded value. Lists are settings of their own.
+fn rebase_edits(edited: Value, applied: &Value, reloaded: Value) -> Value {
+    match (edited, applied, reloaded) {
+        (Value::Mapping(mut edited), Value::Mapping(applied), Value::Mapping(reloaded)) => {
+            let mut rebased = Mapping::new();
+            for (key, reloaded_value) in reloaded {
+                let value = match (edited.remove(&key), applied.get(&key)) {
+                    (Some(edited_value), Some(applied_value)) => {
+                        rebase_edits(edited_value, applied_value, reloaded_value)
+                    }
+                    _ => reloaded_value,
+                };
+                rebased.insert(key, value);
+            }
+            Value::Mapping(rebased)
+        }
+        (edited, applied, reloaded) => {
+            if edited == *applied {
+                reloaded
+            } else {
+                edited
+            }
+        }
+    }
+}
+
+/// Reloads the preferences when the file was edited outside the app. The file has to stay
+/// the same between two checks, so a save still in progress isn't read half written.
+/// Edits pending in the preferences window stay pending on top of the reloaded file.
+fn reload_preferences(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut watch: Local<FileWatch>,
+    preferences_file: Option<ResMut<PreferencesFile>>,
+    mut preferences: EditedPreferences,
+    mut pending_save: ResMut<PendingSave>,
+    mut problems: ResMut<PreferencesProblems>,
+) {
+    let now = time.elapsed_seconds();
+    if now - watch.last_check < WATCH_INTERVAL {
+        return;
+    }
+    watch.last_check = now;
+
+    let Some(mut preferences_file) = preferences_file else {
+        return;
+    };
+    let path = preferences_file.path.clone();
+    let stamp = FileStamp::of(&path);
+    // A missing file is being replaced or was deleted, the app keeps what it has.
+    if stamp.is_none() || stamp == preferences_file.stamp {
+        watch.settling_stamp = None;
+        return;
+    }
+    if watch.settling_stamp != stamp {
+        watch.settling_stamp = stamp;
+        return;
+    }
+    watch.settling_stamp = None;
+    preferences_file.stamp = stamp;
+
+    let parsed = match read_preferences_file(&path) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            problems.report(format!(
+                "Failed to reload the preferences from {path:?}: {err}"
+            ));
+            return;
+        }
+    };
+    if !parsed.problems.is_empty() {
+        for problem in parsed.problems {
+            problems.report(format!("{path:?}: {problem}"));
+        }
+        problems.report(format!(
+            "The edit of {path:?} was not applied, the preferences stay as they were"
+        ));
+        // A newer app took the file over, saving would undo its upgrade.
+        if parsed.newer_version.is_some() {
+            pending_save.0 = None;
+            commands.remove_resource::<PreferencesFile>();
+        }
+        return;
+    }
+
+    // The edited file wins over applied changes that are not saved yet.
+    if pending_save.0.take().is_some() {
+        log::warn!("Unsaved preferences were replaced by the edited file {path:?}");
+    }
+    log::info!("Reloaded the preferences from {path:?}");
+    let as_value =
+        |preferences: &Preferences| serde_yaml::to_value(preferences).unwrap_or_default();
+    let rebased = rebase_edits(
+        as_value(&preferences.edited),
+        &as_value(&preferences.applied),
+        as_value(&parsed.preferences),
+    );
+    *preferences.edited = serde_yaml::from_value(rebased).unwrap_or_else(|err| {
+        log::warn!("Edits pending in the preferences window were dropped: {err}");
+        parsed.preferences.clone()
+    });
+    *preferences.applied = AppliedPreferences(parsed.preferences);
+}
+
+fn apply_bookmarks(
+    time: Res<Time<Real>>,
+    mut event: EventReader<ApplyBookmarksEvent>,
+    preferences: Res<Preferences>,
+    mut applied_preferences: ResMut<AppliedPreferences>,
+    mut pending_save: ResMut<PendingSave>,
+) {
+    if event.read().last().is_none() {
+        return;
+    }
+    applied_preferences
+        .camera_bookmarks
+        .clone_from(&preferences.camera_bookmarks);
+    pending_save.0 = Some(time.elapsed_seconds() + SAVE_DELAY);
+}
+
 fn exempt_preferences(
     mut commands: Commands,
     mut event: EventReader<ExemptPreferencesEvent>,
@@ -85,97 +403,207 @@ fn exempt_preferences(
 
 fn apply_preferences(
     mut commands: Commands,
+    time: Res<Time<Real>>,
     mut event: EventReader<ApplyPreferencesEvent>,
     preferences: Res<Preferences>,
-    preferences_path: Res<PreferencesPath>,
+    mut pending_save: ResMut<PendingSave>,
 ) {
     for _ in event.read() {
         commands.insert_resource(AppliedPreferences((*preferences).clone()));
-
-        let preferences_path = preferences_path.as_ref().as_ref();
-        let mut file = OpenOptions::new()
-            .write(true)
-            .open(preferences_path)
-            .unwrap_or_else(|err| {
-                panic!(
-                    "Failed to create preferences file ({:#?}) \n error: {:#?}",
-                    preferences_path, err
-                )
-            });
-        file.write_all(serde_yaml::to_string(preferences.as_ref()).unwrap().as_bytes())
-            .unwrap_or_else(|err| {
-                panic!(
-                    "Failed to write to preferences file ({:#?}) \n error: {:#?}",
-                    preferences_path, err
-                )
-            });
+        // Apply clicked again and again is written once it settles.
+        pending_save.0 = Some(time.elapsed_seconds() + SAVE_DELAY);
     }
 }
 
-fn setup(mut commands: Commands) {
-    let exe_path = env::current_exe().expect("Failed to find executable path");
+/// Writes the applied preferences once the save is due, or right away when the app exits.
+fn save_preferences(
+    time: Res<Time<Real>>,
+    mut exit_events: EventReader<AppExit>,
+    applied_preferences: Res<AppliedPreferences>,
+    preferences_file: Option<ResMut<PreferencesFile>>,
+    mut pending_save: ResMut<PendingSave>,
+    mut problems: ResMut<PreferencesProblems>,
+) {
+    let exiting = exit_events.read().last().is_some();
+    let Some(due) = pending_save.0 else {
+        return;
+    };
+    if time.elapsed_seconds() < due && !exiting {
+        return;
+    }
+    pending_save.0 = None;
 
-    let exe_dir = exe_path
-        .parent()
-        .expect("Failed to find executable directory");
+    // Without a writable file the preferences live only in memory.
+    let Some(mut preferences_file) = preferences_file else {
+        return;
+    };
+    let path = preferences_file.path.clone();
 
-    let yaml_path = exe_dir.join(format!("{FILE_NAME}.yaml"));
-    let yml_path = exe_dir.join(format!("{FILE_NAME}.yaml"));
+    // Don't write over edits made outside the app, the reload picks them up instead.
+    let stamp = FileStamp::of(&path);
+    if stamp.is_some() && stamp != preferences_file.stamp {
+        problems.report(format!(
+            "{path:?} was changed outside the app, the preferences were not saved over it and the edit is reloaded"
+        ));
+        return;
+    }
 
-    let preferences = {
-        if yaml_path.exists() {
-            let file = File::open(&yaml_path).unwrap_or_else(|err| {
-                panic!(
-                    "Failed to open exist preferences file ({:#?}) \n error: {:#?}",
-                    &yaml_path, err
-                )
-            });
+    match write_preferences_file(&path, &applied_preferences) {
+        Ok(()) => preferences_file.stamp = FileStamp::of(&path),
+        Err(err) => problems.report(format!("Failed to save the preferences to {path:?}: {err}")),
+    }
+}
 
-            commands.insert_resource(PreferencesPath(yaml_path.clone().into()));
+/// Copies a preferences file that had to be repaired next to it, before it is overwritten.
+/// A backup made in the same second gets a counter, earlier backups are never overwritten.
+fn backup_preferences_file(path: &Path) -> io::Result<PathBuf> {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let mut attempt = 0;
+    loop {
+        let mut backup = path.as_os_str().to_owned();
+        match attempt {
+            0 => backup.push(format!(".{seconds}.bak")),
+            _ => backup.push(format!(".{seconds}-{attempt}.bak")),
+        }
+        let backup = PathBuf::from(backup);
 
-            serde_yaml::from_reader(file).unwrap_or_else(|err| {
-                panic!(
-                    "Failed to read preferences file ({:#?}) \n error: {:#?}",
-                    &yaml_path, err
-                )
-            })
-        } else if yml_path.exists() {
-            let file = File::open(&yml_path).unwrap_or_else(|err| {
-                panic!(
-                    "Failed to open exist preferences file ({:#?}) \n error: {:#?}",
-                    &yml_path, err
-                )
-            });
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&backup)
+        {
+            Ok(mut file) => {
+                io::copy(&mut File::open(path)?, &mut file)?;
+                return Ok(backup);
+            }
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => attempt += 1,
+            Err(err) => return Err(err),
+        }
+    }
+}
 
-            commands.insert_resource(PreferencesPath(yml_path.clone().into()));
+/// Reads the existing preferences file, falling back to the defaults for what can't be used.
+/// Also returns whether the app may write the file.
+fn load_preferences_file(path: &Path, problems: &mut PreferencesProblems) -> (Preferences, bool) {
+    let parsed = match read_preferences_file(path) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            problems.report(format!(
+                "Failed to read the preferences from {path:?}, started with the defaults: {err}"
+            ));
+            return (Preferences::default(), true);
+        }
+    };
+    if parsed.problems.is_empty() && parsed.migrated_from.is_none() {
+        return (parsed.preferences, true);
+    }
 
-            serde_yaml::from_reader(&file).unwrap_or_else(|err| {
-                panic!(
-                    "Failed to read preferences file ({:#?}) \n error: {:#?}",
-                    &yml_path, err
-                )
-            })
-        } else {
-            let mut file: File = File::create(&yaml_path).unwrap_or_else(|err| {
-                panic!(
-                    "Failed to create preferences file ({:#?}) \n error: {:#?}",
-                    &yaml_path, err
-                )
-            });
+    for problem in parsed.problems.iter() {
+        problems.report(format!("{path:?}: {problem}"));
+    }
+    // The file belongs to a newer app, it is neither repaired nor saved over.
+    if parsed.newer_version.is_some() {
+        problems.report(format!(
+            "{path:?} is kept as it is, changes to the preferences are kept in memory only"
+        ));
+        return (parsed.preferences, false);
+    }
+    // Keep the original, older versions of the app can't read the upgraded file.
+    match backup_preferences_file(path) {
+        Ok(backup) => {
+            match parsed.migrated_from {
+                Some(version) => log::info!(
+                    "Upgraded the preferences from version {version} to {PREFERENCES_VERSION}, the original file is kept as {backup:?}"
+                ),
+                None => problems.report(format!("The original preferences file is kept as {backup:?}")),
+            }
+            if let Err(err) = write_preferences_file(path, &parsed.preferences) {
+                problems.report(format!(
+                    "Failed to save the repaired preferences to {path:?}: {err}"
+                ));
+            }
+        }
+        // Without a copy the file is left as it is, for the user to fix.
+        Err(err) => problems.report(format!("Failed to back up {path:?}: {err}")),
+    }
+    (parsed.preferences, true)
+}
+
+fn setup(mut commands: Commands, mut problems: ResMut<PreferencesProblems>) {
+    let Some(path) = preferences_path() else {
+        log::warn!("Found no config directory, the preferences are kept in memory only");
+        commands.insert_resource(Preferences::default());
+        return;
+    };
 
-            commands.insert_resource(PreferencesPath(yaml_path.clone().into()));
+    let preferences = if path.exists() {
+        let (preferences, writable) = load_preferences_file(&path, &mut problems);
 
-            let preferences = Preferences::default();
-            serde_yaml::to_writer(&mut file, &preferences).unwrap_or_else(|err| {
-                panic!(
-                    "Failed to write to preferences file ({:#?}) \n error: {:#?}",
-                    &yaml_path, err
-                )
-            });
+        // Opening for appending changes nothing, it only checks the file can be written.
+        match OpenOptions::new().append(true).open(&path) {
+            Ok(_) if writable => commands.insert_resource(PreferencesFile::new(path)),
+            Ok(_) => {}
+            Err(err) => log::warn!(
+                "Preferences file ({:#?}) is read-only, changes are kept in memory only \n error: {:#?}",
+                path,
+                err
+            ),
+        }
 
-            preferences
+        preferences
+    } else {
+        let preferences = system_preferences_path()
+            .map(|system_path| match read_preferences_file(&system_path) {
+                Ok(parsed) => {
+                    for problem in parsed.problems {
+                        problems.report(format!("{system_path:?}: {problem}"));
+                    }
+                    parsed.preferences
+                }
+                Err(err) => {
+                    problems.report(format!("Failed to read {system_path:?}: {err}"));
+                    Preferences::default()
+                }
+            })
+            .unwrap_or_default();
+
+        match write_preferences_file(&path, &preferences) {
+            Ok(()) => commands.insert_resource(PreferencesFile::new(path)),
+            Err(err) => log::warn!(
+                "Failed to create preferences file ({:#?}), the preferences are kept in memory only \n error: {:#?}",
+                path,
+                err
+            ),
         }
+
+        preferences
     };
 
+    commands.insert_resource(AppliedPreferences(preferences.clone()));
     commands.insert_resource(preferences);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn yaml(text: &str) -> Value {
+        serde_yaml::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn reload_keeps_pending_edits() {
+        let applied = yaml("camera: {zoom: 0.5, pan: 0.01}\nbookmarks: [Hall]\nlayout: Single");
+        let edited =
+            yaml("camera: {zoom: 0.8, pan: 0.01}\nbookmarks: [Hall, Attic]\nlayout: Single");
+        let reloaded = yaml("camera: {zoom: 0.6, pan: 0.02}\nbookmarks: [Hall]\nlayout: Two");
+
+        assert_eq!(
+            rebase_edits(edited, &applied, reloaded),
+            yaml("camera: {zoom: 0.8, pan: 0.02}\nbookmarks: [Hall, Attic]\nlayout: Two")
+        );
+    }
+}
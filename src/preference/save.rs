@@ -0,0 +1,55 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use super::Preferences;
+
+/// What the preferences file looked like, to notice when something else changes it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct FileStamp {
+    modified: SystemTime,
+    len: u64,
+}
+
+impl FileStamp {
+    /// Stamp of the file as it is now, None when it doesn't exist.
+    pub(super) fn of(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+        Some(Self {
+            modified: metadata.modified().ok()?,
+            len: metadata.len(),
+        })
+    }
+}
+
+/// Temporary file next to `path` the new contents are written to first.
+fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_owned();
+    name.push(".tmp");
+    path.with_file_name(name)
+}
+
+/// Replaces the preferences file in one step, creating its directory. The contents go to
+/// a temporary file that is renamed over the old one, so a crash or a full disk in the
+/// middle leaves the old file whole and no leftover of it after shorter contents.
+pub(super) fn write_preferences_file(path: &Path, preferences: &Preferences) -> io::Result<()> {
+    let yaml = serde_yaml::to_string(preferences)
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let temp_path = temp_path(path);
+    let written = File::create(&temp_path).and_then(|mut file| {
+        file.write_all(yaml.as_bytes())?;
+        file.sync_all()
+    });
+    if let Err(err) = written.and_then(|()| fs::rename(&temp_path, path)) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(err);
+    }
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_egui::EguiContexts;
+
+use crate::{
+    core::{DeleteBookmarkEvent, RecallBookmarkEvent, SaveBookmarkEvent},
+    preference::Preferences,
+    rich_text,
+    util::DEFAULT_FONT,
+    ui::contmenu::{contmenu_window, Contmenu, ContmenuSubmenu, Side, SubContmenu, DEFAULT_MENU_WIDTH},
+};
+
+pub struct BookmarksMenu;
+
+/// Name typed for the next bookmark.
+#[derive(Resource, Default)]
+struct NewBookmarkName(String);
+
+pub struct BookmarksPlugin;
+
+impl Plugin for BookmarksPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SubContmenu<BookmarksMenu>>()
+            .init_resource::<NewBookmarkName>()
+            .add_systems(
+                Update,
+                ui_context_menu_system.run_if(in_state(ContmenuSubmenu::Bookmarks)),
+            );
+    }
+}
+
+/// What the bookmark entries of the menu can do.
+#[derive(SystemParam)]
+struct BookmarkEvents<'w> {
+    save: EventWriter<'w, SaveBookmarkEvent>,
+    recall: EventWriter<'w, RecallBookmarkEvent>,
+    delete: EventWriter<'w, DeleteBookmarkEvent>,
+}
+
+fn ui_context_menu_system(
+    mut contexts: EguiContexts,
+    context_menu: Res<Contmenu>,
+    mut next_contextmenu_submenu_state: ResMut<NextState<ContmenuSubmenu>>,
+    mut bookmarks_menu: ResMut<SubContmenu<BookmarksMenu>>,
+    mut new_bookmark_name: ResMut<NewBookmarkName>,
+    preferences: Res<Preferences>,
+    mut bookmark_events: BookmarkEvents,
+) {
+    let ctx = contexts.ctx_mut();
+
+    // Get the screen rect
+    let context_menu_rect = context_menu.rect();
+    let screen_rect = ctx.input(|i| i.screen_rect);
+    let bookmarks_menu_width = DEFAULT_MENU_WIDTH;
+    let mut submenu_position = context_menu_rect.min + egui::vec2(context_menu_rect.width(), 0.0);
+
+    // Check if the submenu would go off-screen to the right
+    if submenu_position.x + bookmarks_menu_width > screen_rect.max.x {
+        // Not enough space on the right, so place it to the left
+        submenu_position = context_menu_rect.min - egui::vec2(bookmarks_menu_width, 0.0);
+
+        // Ensure the submenu does not go off-screen to the left
+        if submenu_position.x < screen_rect.min.x {
+            submenu_position.x = screen_rect.min.x;
+        }
+
+        // write that this menu appeared on left
+        bookmarks_menu.appeared(Side::Left);
+    }
+
+    let bookmarks_responce = contmenu_window("Bookmarks Menu", submenu_position).show(ctx, |ui| {
+        ui.with_layout(egui::Layout::top_down_justified(egui::Align::LEFT), |ui| {
+            for bookmark in &preferences.camera_bookmarks {
+                ui.horizontal(|ui| {
+                    if ui.small_button("✖").clicked() {
+                        bookmark_events.delete.send(DeleteBookmarkEvent(bookmark.name.clone()));
+                    }
+                    // User given names are shown as typed, not translated.
+                    let name = egui::RichText::new(&bookmark.name).font(DEFAULT_FONT.clone());
+                    if ui.selectable_label(false, name).clicked() {
+                        bookmark_events.recall.send(RecallBookmarkEvent(bookmark.name.clone()));
+                    }
+                });
+            }
+
+            ui.separator();
+
+            ui.text_edit_singleline(&mut new_bookmark_name.0);
+            let name = new_bookmark_name.0.trim();
+            if ui
+                .add_enabled(!name.is_empty(), egui::Button::new(rich_text!("Save Current View")))
+                .clicked()
+            {
+                bookmark_events.save.send(SaveBookmarkEvent(name.to_string()));
+                new_bookmark_name.0.clear();
+            }
+        });
+    });
+
+    if let Some(bookmarks_rect) = bookmarks_responce {
+        bookmarks_menu.set_rect(bookmarks_rect.response.rect);
+    } else {
+        log::error!("window rect not found");
+    }
+
+    if ctx.input(|i| i.pointer.any_down()) && !ctx.is_pointer_over_area() {
+        next_contextmenu_submenu_state.set(ContmenuSubmenu::Closed);
+    }
+}
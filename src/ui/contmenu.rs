@@ -1,10 +1,17 @@
 use std::marker::PhantomData;
 
-use super::preferences::PreferencesPlugins;
-use bevy::{prelude::*, window::PrimaryWindow};
+use super::{bookmarks::BookmarksPlugin, preferences::PreferencesPlugins, views::ViewsPlugin};
+use bevy::{ecs::system::SystemParam, prelude::*, window::PrimaryWindow};
 use bevy_egui::EguiContexts;
 
-use crate::{input::ExtendedButtonInput, rich_text};
+use crate::{
+    core::{
+        ActiveViewport, CameraController, CameraMode, FrameEvent, FrameTarget, TogglePlanViewEvent,
+        ToggleWalkthroughEvent,
+    },
+    input::{Action, ActionState},
+    rich_text,
+};
 
 pub const DEFAULT_MENU_INNER_WIDTH: f32 = 200.0;
 pub const DEFAULT_MARGIN: egui::Margin = egui::Margin {
@@ -77,6 +84,8 @@ pub enum ContmenuSubmenu {
     #[default]
     Closed,
     Preferences,
+    Bookmarks,
+    Views,
 }
 
 #[derive(Resource)]
@@ -94,6 +103,10 @@ impl Contmenu {
     pub fn get_rect(&self) -> Option<egui::Rect> {
         self.rect
     }
+
+    pub fn close(&mut self) {
+        self.show_menu = false;
+    }
 }
 
 impl Default for Contmenu {
@@ -114,7 +127,7 @@ impl Plugin for ContmenuPlugins {
             .insert_state(ContmenuSubmenu::default())
             .add_systems(Startup, setup_styles)
             .add_systems(Update, (right_click_system, ui_context_menu_system))
-            .add_plugins(PreferencesPlugins);
+            .add_plugins((PreferencesPlugins, BookmarksPlugin, ViewsPlugin));
     }
 }
 
@@ -132,10 +145,10 @@ pub fn contmenu_window<'open>(
 
 fn right_click_system(
     mut context_menu: ResMut<Contmenu>,
-    mouse_button_input: Res<ExtendedButtonInput>,
+    action_state: Res<ActionState>,
     mut q_windows: Query<&mut Window, With<PrimaryWindow>>,
 ) {
-    if mouse_button_input.clicked(MouseButton::Right) {
+    if action_state.just_pressed(Action::OpenContextMenu) {
         let primary_window = q_windows.single_mut();
         if let Some(cursor_position) = primary_window.cursor_position() {
             let egui_position = egui::Pos2 {
@@ -162,18 +175,35 @@ fn setup_styles(mut contexts: EguiContexts) {
     //ctx.set_debug_on_hover(true);
 }
 
+/// Commands the context menu entries send.
+#[derive(SystemParam)]
+struct ContmenuEvents<'w> {
+    plan_view: EventWriter<'w, TogglePlanViewEvent>,
+    walkthrough: EventWriter<'w, ToggleWalkthroughEvent>,
+    frame: EventWriter<'w, FrameEvent>,
+}
+
 fn ui_context_menu_system(
     mut contexts: EguiContexts,
     mut context_menu: ResMut<Contmenu>,
     submenu_state: Res<State<ContmenuSubmenu>>,
     mut next_submenu_state: ResMut<NextState<ContmenuSubmenu>>,
+    camera_query: Query<&CameraController, With<ActiveViewport>>,
+    mut events: ContmenuEvents,
 ) {
     let ctx = contexts.ctx_mut();
 
     use ContmenuSubmenu::*;
 
+    let camera_mode = camera_query
+        .iter()
+        .next()
+        .map(|controller| controller.mode)
+        .unwrap_or_default();
+
     if context_menu.show_menu {
         let mut preferences_button_rect = None;
+        let mut close_menu = false;
 
         let window_response = contmenu_window("Context Menu", context_menu.position)
             .show(ctx, |ui| {
@@ -185,6 +215,54 @@ fn ui_context_menu_system(
                     }
                     // Store the rect of the "Preferences" button
                     preferences_button_rect = Some(preferences_button.rect);
+
+                    let bookmarks_button =
+                        ui.selectable_label(*submenu_state == Bookmarks, rich_text!("Bookmarks"));
+                    if bookmarks_button.hovered() {
+                        next_submenu_state.set(Bookmarks);
+                    }
+
+                    let views_button =
+                        ui.selectable_label(*submenu_state == Views, rich_text!("Views"));
+                    if views_button.hovered() {
+                        next_submenu_state.set(Views);
+                    }
+
+                    let plan_view_button = ui
+                        .selectable_label(camera_mode == CameraMode::Plan, rich_text!("Plan View"));
+                    if plan_view_button.hovered() {
+                        next_submenu_state.set(Closed);
+                    }
+                    if plan_view_button.clicked() {
+                        events.plan_view.send(TogglePlanViewEvent);
+                        close_menu = true;
+                    }
+
+                    let walkthrough_button = ui.selectable_label(
+                        camera_mode == CameraMode::Walkthrough,
+                        rich_text!("Walkthrough"),
+                    );
+                    if walkthrough_button.hovered() {
+                        next_submenu_state.set(Closed);
+                    }
+                    if walkthrough_button.clicked() {
+                        events.walkthrough.send(ToggleWalkthroughEvent);
+                        close_menu = true;
+                    }
+
+                    for (label, target) in [
+                        ("Frame All", FrameTarget::All),
+                        ("Frame Selected", FrameTarget::Selected),
+                    ] {
+                        let frame_button = ui.selectable_label(false, rich_text!(label));
+                        if frame_button.hovered() {
+                            next_submenu_state.set(Closed);
+                        }
+                        if frame_button.clicked() {
+                            events.frame.send(FrameEvent(target));
+                            close_menu = true;
+                        }
+                    }
                 });
             });
 
@@ -194,8 +272,8 @@ fn ui_context_menu_system(
             log::error!("window rect not found");
         }
 
-        // Close the context menus if clicked elsewhere
-        if ctx.input(|i| i.pointer.any_down()) && !ctx.is_pointer_over_area() {
+        // Close the context menus if clicked elsewhere or an action was chosen
+        if close_menu || ctx.input(|i| i.pointer.any_down()) && !ctx.is_pointer_over_area() {
             context_menu.show_menu = false;
             next_submenu_state.set(ContmenuSubmenu::Closed);
         }
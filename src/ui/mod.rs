@@ -1,7 +1,10 @@
 #![allow(clippy::module_inception)]
 
+mod bookmarks;
 mod contmenu;
+mod nav_cube;
 mod preferences;
 mod ui;
+mod views;
 
 pub use ui::*;
@@ -0,0 +1,164 @@
+use bevy::prelude::*;
+use bevy_egui::EguiContexts;
+
+use crate::{
+    core::{CameraController, EditorCamera, LookFromEvent, OrbitViewEvent},
+    util::DEFAULT_FONT,
+};
+
+/// Half the edge of the cube in points.
+const CUBE_SIZE: f32 = 28.0;
+/// Distance of the cube center from the viewport's top right corner.
+const CUBE_MARGIN: f32 = 60.0;
+/// Part of a face near its border that picks the edge or corner instead of the face.
+const EDGE_BAND: f32 = 0.35;
+/// Radians the camera orbits per point the cube is dragged.
+const DRAG_SENSITIVITY: f32 = 0.01;
+
+/// Sides of the cube: outward normal, the two in-plane axes and the label.
+const FACES: [(Vec3, Vec3, Vec3, &str); 6] = [
+    (Vec3::Y, Vec3::X, Vec3::NEG_Z, "Top"),
+    (Vec3::NEG_Y, Vec3::X, Vec3::Z, "Bottom"),
+    (Vec3::Z, Vec3::X, Vec3::Y, "Front"),
+    (Vec3::NEG_Z, Vec3::NEG_X, Vec3::Y, "Back"),
+    (Vec3::X, Vec3::NEG_Z, Vec3::Y, "Right"),
+    (Vec3::NEG_X, Vec3::Z, Vec3::Y, "Left"),
+];
+
+pub struct NavCubePlugin;
+
+impl Plugin for NavCubePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, nav_cube_system);
+    }
+}
+
+/// Cube drawn with the camera orientation, projected orthographically onto the screen.
+struct CubeProjection {
+    center: egui::Pos2,
+    /// World to camera rotation.
+    inverse_rotation: Quat,
+}
+
+impl CubeProjection {
+    fn view(&self, point: Vec3) -> Vec3 {
+        self.inverse_rotation * point
+    }
+
+    fn screen(&self, point: Vec3) -> egui::Pos2 {
+        let view = self.view(point);
+        self.center + egui::vec2(view.x, -view.y) * CUBE_SIZE
+    }
+
+    fn screen_vec(&self, direction: Vec3) -> Vec2 {
+        let view = self.view(direction);
+        Vec2::new(view.x, -view.y) * CUBE_SIZE
+    }
+
+    /// Faces turned towards the camera.
+    fn visible_faces(
+        &self,
+    ) -> impl Iterator<Item = &'static (Vec3, Vec3, Vec3, &'static str)> + '_ {
+        FACES
+            .iter()
+            .filter(|(normal, ..)| self.view(*normal).z > 0.)
+    }
+
+    /// The face, edge or corner under `position` as the direction to look from.
+    fn pick(&self, position: egui::Pos2) -> Option<Vec3> {
+        self.visible_faces()
+            .find_map(|(normal, u_axis, v_axis, _)| {
+                // Solve `position = center(normal) + u * u_axis + v * v_axis` on screen.
+                let offset = position - self.screen(*normal);
+                let offset = Vec2::new(offset.x, offset.y);
+                let axes = Mat2::from_cols(self.screen_vec(*u_axis), self.screen_vec(*v_axis));
+                if axes.determinant().abs() < f32::EPSILON {
+                    return None;
+                }
+                let local = axes.inverse() * offset;
+                if local.abs().max_element() > 1. {
+                    return None;
+                }
+
+                let snap = |coordinate: f32| {
+                    if coordinate.abs() > 1. - EDGE_BAND {
+                        coordinate.signum()
+                    } else {
+                        0.
+                    }
+                };
+                Some(*normal + *u_axis * snap(local.x) + *v_axis * snap(local.y))
+            })
+    }
+}
+
+fn nav_cube_system(
+    mut contexts: EguiContexts,
+    query: Query<(Entity, &CameraController, &Camera), With<EditorCamera>>,
+    mut look_from_event: EventWriter<LookFromEvent>,
+    mut orbit_event: EventWriter<OrbitViewEvent>,
+) {
+    let ctx = contexts.ctx_mut();
+
+    for (entity, controller, camera) in query.iter() {
+        let Some(viewport) = camera.logical_viewport_rect() else {
+            continue;
+        };
+
+        let cube = CubeProjection {
+            center: egui::pos2(viewport.max.x - CUBE_MARGIN, viewport.min.y + CUBE_MARGIN),
+            inverse_rotation: controller.rotation().inverse(),
+        };
+        let area_size = egui::Vec2::splat(CUBE_MARGIN * 2.);
+
+        egui::Area::new(egui::Id::new(("nav_cube", entity)))
+            .fixed_pos(cube.center - area_size / 2.)
+            .show(ctx, |ui| {
+                let (response, painter) =
+                    ui.allocate_painter(area_size, egui::Sense::click_and_drag());
+                let hovered = response
+                    .hover_pos()
+                    .and_then(|position| cube.pick(position));
+
+                let visuals = ui.visuals();
+                for (normal, u_axis, v_axis, label) in cube.visible_faces() {
+                    let corners = [(-1., -1.), (1., -1.), (1., 1.), (-1., 1.)]
+                        .map(|(u, v)| cube.screen(*normal + *u_axis * u + *v_axis * v));
+
+                    let fill = if hovered.map_or(false, |direction| direction.dot(*normal) > 0.) {
+                        visuals.selection.bg_fill
+                    } else {
+                        visuals.widgets.inactive.bg_fill
+                    };
+                    painter.add(egui::Shape::convex_polygon(
+                        corners.to_vec(),
+                        fill,
+                        visuals.widgets.inactive.fg_stroke,
+                    ));
+
+                    let mut font = DEFAULT_FONT.clone();
+                    font.size = 9.0;
+                    painter.text(
+                        cube.screen(*normal),
+                        egui::Align2::CENTER_CENTER,
+                        *label,
+                        font,
+                        visuals.text_color(),
+                    );
+                }
+
+                if response.dragged() {
+                    let delta = response.drag_delta();
+                    orbit_event.send(OrbitViewEvent(
+                        -Vec2::new(delta.x, delta.y) * DRAG_SENSITIVITY,
+                    ));
+                } else if response.clicked() {
+                    if let Some(direction) =
+                        response.interact_pointer_pos().and_then(|p| cube.pick(p))
+                    {
+                        look_from_event.send(LookFromEvent(direction));
+                    }
+                }
+            });
+    }
+}
@@ -1,9 +1,11 @@
 use bevy::{prelude::*, window::PrimaryWindow};
 use bevy_egui::{EguiContext, EguiContexts, EguiPlugin};
+use strum::IntoEnumIterator;
 
 use crate::{
     preference::{
-        ApplyPreferencesEvent, CameraControllPreferences, ExemptPreferencesEvent, Preferences,
+        ApplyPreferencesEvent, CameraControlPreferences, ExemptPreferencesEvent, NavigationPreset,
+        Preferences, ViewportLayout,
     },
     rich_text,
     ui::contmenu::{contmenu_window, Side, SubContmenu, DEFAULT_MENU_INNER_WIDTH, DEFAULT_MENU_WIDTH},
@@ -66,17 +68,36 @@ fn ui_context_menu_system(
         camera_controll_menu.appeared(Side::Left);
     }
 
-    let camera_prefs = &mut preferences.camera_controll;
+    let Preferences {
+        camera_control: camera_prefs,
+        viewport_layout,
+        ..
+    } = &mut *preferences;
 
     let camera_controll_response =
         contmenu_window("Camera Controll Menu", submenu_position).show(ctx, |ui| {
             ui.with_layout(egui::Layout::top_down_justified(egui::Align::LEFT), |ui| {
+                ui.label(rich_text!("Navigation Preset"));
+                egui::ComboBox::from_id_source("navigation_preset")
+                    .selected_text(rich_text!(camera_prefs.navigation_preset.as_ref()))
+                    .show_ui(ui, |ui| {
+                        for preset in NavigationPreset::iter() {
+                            ui.selectable_value(
+                                &mut camera_prefs.navigation_preset,
+                                preset,
+                                rich_text!(preset.as_ref()),
+                            );
+                        }
+                    });
+
                 ui.label(rich_text!("Rotation Sensitivity"));
                 ui.add(
                     egui::Slider::new(&mut camera_prefs.rotation_sensitivity, 0.0..=0.10)
                         .text(rich_text!("Rotation Sensitivity")),
                 );
 
+                ui.checkbox(&mut camera_prefs.orbit_around_cursor, rich_text!("Orbit Around Cursor"));
+
                 ui.label(rich_text!("Pan Sensitivity"));
                 ui.add(
                     egui::Slider::new(&mut camera_prefs.pan_sensitivity, 0.0..=10.0)
@@ -89,31 +110,130 @@ fn ui_context_menu_system(
                         .text(rich_text!("Zoom Sensitivity")),
                 );
 
+                ui.checkbox(&mut camera_prefs.zoom_to_cursor, rich_text!("Zoom To Cursor"));
+
                 ui.label(rich_text!("Min Distance"));
                 ui.add(
-                    egui::Slider::new(&mut camera_prefs.min_distance, 0.0..=100.0)
+                    egui::Slider::new(&mut camera_prefs.min_distance, 0.1..=100.0)
+                        .logarithmic(true)
                         .text(rich_text!("Min Distance")),
                 );
 
                 ui.label(rich_text!("Max Distance"));
                 ui.add(
-                    egui::Slider::new(&mut camera_prefs.max_distance, 0.0..=1000.0)
+                    egui::Slider::new(&mut camera_prefs.max_distance, 1.0..=1000.0)
+                        .logarithmic(true)
                         .text(rich_text!("Max Distance")),
                 );
 
+                ui.checkbox(&mut camera_prefs.pixel_scroll_pans, rich_text!("Pixel Scroll Pans"));
+
+                ui.label(rich_text!("Touch Pan Sensitivity"));
+                ui.add(
+                    egui::Slider::new(&mut camera_prefs.touch_pan_sensitivity, 0.0..=5.0)
+                        .text(rich_text!("Touch Pan Sensitivity")),
+                );
+
+                ui.label(rich_text!("Pinch Sensitivity"));
+                ui.add(
+                    egui::Slider::new(&mut camera_prefs.pinch_sensitivity, 0.0..=5.0)
+                        .text(rich_text!("Pinch Sensitivity")),
+                );
+
+                ui.label(rich_text!("Touch Rotation Sensitivity"));
+                ui.add(
+                    egui::Slider::new(&mut camera_prefs.touch_rotation_sensitivity, 0.0..=5.0)
+                        .text(rich_text!("Touch Rotation Sensitivity")),
+                );
+
+                ui.label(rich_text!("Gamepad Dead Zone"));
+                ui.add(
+                    egui::Slider::new(&mut camera_prefs.gamepad_dead_zone, 0.0..=0.9)
+                        .text(rich_text!("Gamepad Dead Zone")),
+                );
+
+                ui.label(rich_text!("Gamepad Look Sensitivity"));
+                ui.add(
+                    egui::Slider::new(&mut camera_prefs.gamepad_look_sensitivity, 0.1..=10.0)
+                        .text(rich_text!("Gamepad Look Sensitivity")),
+                );
+
+                ui.label(rich_text!("Gamepad Move Sensitivity"));
+                ui.add(
+                    egui::Slider::new(&mut camera_prefs.gamepad_move_sensitivity, 0.1..=10.0)
+                        .text(rich_text!("Gamepad Move Sensitivity")),
+                );
+
+                ui.checkbox(&mut camera_prefs.limits_on, rich_text!("Limits On"));
+
+                ui.label(rich_text!("Min Height (cm)"));
+                ui.add(
+                    egui::Slider::new(&mut camera_prefs.min_height_cm, 0.0..=500.0)
+                        .text(rich_text!("Min Height (cm)")),
+                );
+
+                ui.label(rich_text!("Min Pitch (°)"));
+                ui.add(
+                    egui::Slider::new(&mut camera_prefs.min_pitch_degrees, -89.9..=0.0)
+                        .text(rich_text!("Min Pitch (°)")),
+                );
+
+                ui.label(rich_text!("Max Pitch (°)"));
+                ui.add(
+                    egui::Slider::new(&mut camera_prefs.max_pitch_degrees, 0.0..=89.9)
+                        .text(rich_text!("Max Pitch (°)")),
+                );
+
+                ui.label(rich_text!("Bounds Margin (cm)"));
+                ui.add(
+                    egui::Slider::new(&mut camera_prefs.bounds_margin_cm, 0.0..=1000.0)
+                        .text(rich_text!("Bounds Margin (cm)")),
+                );
+
                 ui.checkbox(&mut camera_prefs.inertia_on, rich_text!("Inertia On"));
 
-                ui.label(rich_text!("Inertia Decrement Speed"));
+                ui.label(rich_text!("Inertia Damping"));
+                ui.add(
+                    egui::Slider::new(&mut camera_prefs.inertia_damping, 0.5..=30.0)
+                        .text(rich_text!("Inertia Damping")),
+                );
+
+                ui.label(rich_text!("Transition Duration"));
                 ui.add(
-                    egui::Slider::new(&mut camera_prefs.inertia_decrement_speed, 0.0..=1.0)
-                        .text(rich_text!("Inertia Decrement Speed")),
+                    egui::Slider::new(&mut camera_prefs.transition_duration, 0.0..=3.0)
+                        .text(rich_text!("Transition Duration")),
                 );
+
+                ui.label(rich_text!("Eye Height (cm)"));
+                ui.add(
+                    egui::Slider::new(&mut camera_prefs.eye_height_cm, 50.0..=250.0)
+                        .text(rich_text!("Eye Height (cm)")),
+                );
+
+                ui.label(rich_text!("Walk Speed (cm/s)"));
+                ui.add(
+                    egui::Slider::new(&mut camera_prefs.walk_speed_cm, 10.0..=1000.0)
+                        .text(rich_text!("Walk Speed (cm/s)")),
+                );
+
+                ui.label(rich_text!("Turn Step (°)"));
+                ui.add(
+                    egui::Slider::new(&mut camera_prefs.turn_step_degrees, 1.0..=90.0)
+                        .text(rich_text!("Turn Step (°)")),
+                );
+                ui.label(rich_text!("Viewport Layout"));
+                ui.horizontal(|ui| {
+                    for layout in ViewportLayout::iter() {
+                        ui.radio_value(viewport_layout, layout, rich_text!(layout.as_ref()));
+                    }
+                });
+
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::LEFT), |ui| {
                     if ui.button(rich_text!("Apply")).clicked() {
                         apply_event.send(ApplyPreferencesEvent);
                     }
                     if ui.button(rich_text!("Default")).clicked() {
-                        *camera_prefs = CameraControllPreferences::default();
+                        *camera_prefs = CameraControlPreferences::default();
                     }
                 });
             });
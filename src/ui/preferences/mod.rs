@@ -2,5 +2,6 @@
 
 mod preferences;
 mod camera_controll;
+mod problems;
 
 pub use preferences::*;
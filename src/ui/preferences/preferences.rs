@@ -7,7 +7,7 @@ use crate::{
 use bevy::prelude::*;
 use bevy_egui::EguiContexts;
 
-use super::camera_controll::CameraMovementPlugin;
+use super::{camera_controll::CameraMovementPlugin, problems::PreferencesProblemsPlugin};
 
 pub struct PreferencesMenu;
 
@@ -28,7 +28,7 @@ impl Plugin for PreferencesPlugins {
                 Update,
                 ui_context_menu_system.run_if(in_state(ContmenuSubmenu::Preferences)),
             )
-            .add_plugins(CameraMovementPlugin);
+            .add_plugins((CameraMovementPlugin, PreferencesProblemsPlugin));
     }
 }
 
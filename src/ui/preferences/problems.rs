@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+use bevy_egui::EguiContexts;
+
+use crate::{preference::PreferencesProblems, rich_text, util::DEFAULT_FONT};
+
+pub struct PreferencesProblemsPlugin;
+
+impl Plugin for PreferencesProblemsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            problems_window_system.run_if(|problems: Res<PreferencesProblems>| !problems.0.is_empty()),
+        );
+    }
+}
+
+/// Lists what went wrong with the preferences file until the user dismisses it.
+fn problems_window_system(mut contexts: EguiContexts, mut problems: ResMut<PreferencesProblems>) {
+    let ctx = contexts.ctx_mut();
+
+    egui::Window::new(rich_text!("Preferences Problems"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_TOP, egui::vec2(0., 16.))
+        .show(ctx, |ui| {
+            ui.with_layout(egui::Layout::top_down(egui::Align::LEFT), |ui| {
+                // Messages hold paths and values, they are shown untranslated.
+                for problem in &problems.0 {
+                    ui.label(egui::RichText::new(problem).font(DEFAULT_FONT.clone()));
+                }
+
+                ui.separator();
+
+                if ui.button(rich_text!("Dismiss")).clicked() {
+                    problems.0.clear();
+                }
+            });
+        });
+}
@@ -2,13 +2,13 @@ use std::sync::Arc;
 
 use bevy::prelude::*;
 
-use super::contmenu::ContmenuPlugins;
+use super::{contmenu::ContmenuPlugins, nav_cube::NavCubePlugin};
 
 pub struct UiPlugins;
 
 impl Plugin for UiPlugins {
   fn build(&self, app: &mut App) {
-    app.add_plugins(ContmenuPlugins);
+    app.add_plugins((ContmenuPlugins, NavCubePlugin));
   }
 }
 
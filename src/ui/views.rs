@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+use bevy_egui::EguiContexts;
+use strum::IntoEnumIterator;
+
+use crate::{
+    core::{StandardView, StandardViewEvent},
+    rich_text,
+    ui::contmenu::{contmenu_window, Contmenu, ContmenuSubmenu, Side, SubContmenu, DEFAULT_MENU_WIDTH},
+};
+
+pub struct ViewsMenu;
+
+pub struct ViewsPlugin;
+
+impl Plugin for ViewsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SubContmenu<ViewsMenu>>().add_systems(
+            Update,
+            ui_context_menu_system.run_if(in_state(ContmenuSubmenu::Views)),
+        );
+    }
+}
+
+fn ui_context_menu_system(
+    mut contexts: EguiContexts,
+    mut context_menu: ResMut<Contmenu>,
+    mut next_contextmenu_submenu_state: ResMut<NextState<ContmenuSubmenu>>,
+    mut views_menu: ResMut<SubContmenu<ViewsMenu>>,
+    mut view_event: EventWriter<StandardViewEvent>,
+) {
+    let ctx = contexts.ctx_mut();
+
+    // Get the screen rect
+    let context_menu_rect = context_menu.rect();
+    let screen_rect = ctx.input(|i| i.screen_rect);
+    let views_menu_width = DEFAULT_MENU_WIDTH;
+    let mut submenu_position = context_menu_rect.min + egui::vec2(context_menu_rect.width(), 0.0);
+
+    // Check if the submenu would go off-screen to the right
+    if submenu_position.x + views_menu_width > screen_rect.max.x {
+        // Not enough space on the right, so place it to the left
+        submenu_position = context_menu_rect.min - egui::vec2(views_menu_width, 0.0);
+
+        // Ensure the submenu does not go off-screen to the left
+        if submenu_position.x < screen_rect.min.x {
+            submenu_position.x = screen_rect.min.x;
+        }
+
+        // write that this menu appeared on left
+        views_menu.appeared(Side::Left);
+    }
+
+    let mut chosen = None;
+
+    let views_responce = contmenu_window("Views Menu", submenu_position).show(ctx, |ui| {
+        ui.with_layout(egui::Layout::top_down_justified(egui::Align::LEFT), |ui| {
+            for view in StandardView::iter() {
+                if ui.selectable_label(false, rich_text!(view.as_ref())).clicked() {
+                    chosen = Some(view);
+                }
+            }
+        });
+    });
+
+    if let Some(views_rect) = views_responce {
+        views_menu.set_rect(views_rect.response.rect);
+    } else {
+        log::error!("window rect not found");
+    }
+
+    if let Some(view) = chosen {
+        view_event.send(StandardViewEvent(view));
+        context_menu.close();
+        next_contextmenu_submenu_state.set(ContmenuSubmenu::Closed);
+    } else if ctx.input(|i| i.pointer.any_down()) && !ctx.is_pointer_over_area() {
+        next_contextmenu_submenu_state.set(ContmenuSubmenu::Closed);
+    }
+}
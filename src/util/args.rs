@@ -0,0 +1,23 @@
+use std::env;
+
+/// Value of `flag` on the command line, `--flag value` or `--flag=value`.
+pub fn flag_value(flag: &str) -> Option<String> {
+    let mut args = env::args();
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            return args.next();
+        }
+        if let Some(value) = arg
+            .strip_prefix(flag)
+            .and_then(|rest| rest.strip_prefix('='))
+        {
+            return Some(value.to_owned());
+        }
+    }
+    None
+}
+
+/// Whether `flag` is on the command line.
+pub fn has_flag(flag: &str) -> bool {
+    env::args().any(|arg| arg == flag)
+}
@@ -1,8 +1,10 @@
+mod args;
 mod debug;
 mod hash_map;
 mod i18n;
 mod ui;
 
+pub use args::*;
 pub use debug::*;
 pub use hash_map::*;
 pub use i18n::*;
@@ -0,0 +1,175 @@
+//! Preferences files of every schema version load into the current preferences.
+
+use std::fs;
+
+use andreo::{
+    input::{Action, Binding, InputBindings, InputTrigger, Modifiers},
+    preference::{
+        migrate_preferences, parse_preferences, CameraControlPreferences, NavigationPreset,
+        ParsedPreferences, ViewportLayout, PREFERENCES_VERSION,
+    },
+};
+use bevy::prelude::MouseButton;
+use serde_yaml::{Mapping, Value};
+
+fn fixture_text(version: u32) -> String {
+    let path = format!(
+        "{}/tests/fixtures/preferences/v{version}.yaml",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    fs::read_to_string(&path).unwrap_or_else(|err| panic!("{path}: {err}"))
+}
+
+fn load_fixture(version: u32) -> ParsedPreferences {
+    parse_preferences(&fixture_text(version))
+}
+
+#[test]
+fn every_version_has_a_fixture() {
+    for version in 0..=PREFERENCES_VERSION {
+        load_fixture(version);
+    }
+}
+
+/// The version 0 fixture is what the first release wrote with its defaults.
+#[test]
+fn version_0_is_migrated() {
+    let parsed = load_fixture(0);
+    assert_eq!(parsed.problems, Vec::<String>::new());
+    assert_eq!(parsed.migrated_from, Some(0));
+
+    let preferences = &parsed.preferences;
+    assert_eq!(preferences.version, PREFERENCES_VERSION);
+
+    let camera = &preferences.camera_control;
+    assert_eq!(camera.rotation_sensitivity, 0.005);
+    assert_eq!(camera.pan_sensitivity, 0.01);
+    assert_eq!(camera.zoom_sensitivity, 0.5);
+    assert_eq!(camera.min_distance, 1.0);
+    assert_eq!(camera.max_distance, 100.0);
+    assert!(!camera.inertia_on);
+    // Settings the first release didn't have get their defaults.
+    let default = CameraControlPreferences::default();
+    assert_eq!(camera.inertia_damping, default.inertia_damping);
+    assert_eq!(camera.navigation_preset, NavigationPreset::default());
+    assert_eq!(camera.bounds_margin_cm, default.bounds_margin_cm);
+
+    assert_eq!(preferences.input_bindings, InputBindings::default());
+    assert!(preferences.camera_bookmarks.is_empty());
+}
+
+#[test]
+fn version_0_drops_obsolete_settings() {
+    let Ok(Value::Mapping(mut preferences)) = serde_yaml::from_str(&fixture_text(0)) else {
+        panic!("the version 0 fixture is not a mapping");
+    };
+    let mut problems = Vec::new();
+    assert_eq!(migrate_preferences(&mut preferences, &mut problems), Ok(0));
+    assert_eq!(problems, Vec::<String>::new());
+
+    let camera = preferences
+        .get("camera_control")
+        .and_then(Value::as_mapping)
+        .expect("the camera section is renamed");
+    assert!(!camera.contains_key("inertia_decrement_speed"));
+    assert!(!preferences.contains_key("camera_controll"));
+}
+
+/// Version 0 zoomed by a fixed distance a wheel step, a custom zoom doesn't carry over.
+#[test]
+fn version_0_custom_zoom_is_reset() {
+    let parsed = parse_preferences("camera_controll:\n  zoom_sensitivity: 2.0\n");
+    assert_eq!(parsed.problems.len(), 1);
+    assert_eq!(
+        parsed.preferences.camera_control.zoom_sensitivity,
+        CameraControlPreferences::default().zoom_sensitivity
+    );
+}
+
+#[test]
+fn version_1_loads_as_is() {
+    let parsed = load_fixture(1);
+    assert_eq!(parsed.problems, Vec::<String>::new());
+    assert_eq!(parsed.migrated_from, None);
+
+    let preferences = &parsed.preferences;
+    assert_eq!(preferences.version, PREFERENCES_VERSION);
+
+    let camera = &preferences.camera_control;
+    assert_eq!(camera.rotation_sensitivity, 0.004);
+    assert!(camera.orbit_around_cursor);
+    assert_eq!(camera.pan_sensitivity, 0.02);
+    assert_eq!(camera.min_distance, 2.0);
+    assert_eq!(camera.max_distance, 80.0);
+    assert!(camera.inertia_on);
+    assert_eq!(camera.eye_height_cm, 170.0);
+    assert_eq!(camera.navigation_preset, NavigationPreset::Blender);
+    assert_eq!(camera.bounds_margin_cm, 50.0);
+
+    // The file binds no actions, they get their defaults and the drags of its preset.
+    let bindings = &preferences.input_bindings;
+    assert_eq!(*bindings, InputBindings::default());
+    assert_eq!(
+        bindings.get(Action::CameraZoom, camera.navigation_preset),
+        [Binding::new(
+            InputTrigger::Mouse(MouseButton::Middle),
+            Modifiers::CTRL
+        )]
+    );
+
+    assert_eq!(preferences.viewport_layout, ViewportLayout::Two);
+    assert_eq!(preferences.camera_bookmarks.len(), 1);
+    assert_eq!(preferences.camera_bookmarks[0].name, "Kitchen");
+}
+
+/// The navigation preset binds the camera drags, stored ones don't override it.
+#[test]
+fn camera_drags_follow_the_preset() {
+    let parsed = parse_preferences(
+        "version: 1
+camera_control:
+  navigation_preset: Blender
+input_bindings:
+- action: CameraOrbit
+  bindings:
+  - trigger: !Mouse Right
+",
+    );
+    assert_eq!(parsed.problems, Vec::<String>::new());
+
+    let bindings = &parsed.preferences.input_bindings;
+    assert!(bindings
+        .0
+        .iter()
+        .all(|entry| !entry.action.follows_preset()));
+    assert_eq!(
+        bindings.get(Action::CameraOrbit, NavigationPreset::Blender),
+        [Binding::new(
+            InputTrigger::Mouse(MouseButton::Middle),
+            Modifiers::NONE
+        )]
+    );
+}
+
+#[test]
+fn newer_version_is_read_only() {
+    let newer = PREFERENCES_VERSION + 1;
+    let mut preferences = Mapping::new();
+    preferences.insert("version".into(), newer.into());
+    preferences.insert("unknown_section".into(), true.into());
+    let original = preferences.clone();
+
+    // The migrations leave a newer file alone.
+    let mut problems = Vec::new();
+    assert_eq!(
+        migrate_preferences(&mut preferences, &mut problems),
+        Ok(newer)
+    );
+    assert_eq!(preferences, original);
+    assert_eq!(problems, Vec::<String>::new());
+
+    let parsed = parse_preferences(&format!("version: {newer}\n"));
+    assert_eq!(parsed.problems.len(), 1);
+    assert_eq!(parsed.migrated_from, None);
+    assert_eq!(parsed.newer_version, Some(newer));
+}